@@ -4,53 +4,115 @@
 //!
 //! [More about Adler-32](https://en.wikipedia.org/wiki/Adler-32)
 
+use crate::license_checksum::LicenseChecksumAlgorithm;
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
 use crate::magic::Result;
-use byteorder::{BigEndian, ReadBytesExt};
-use simple_error::bail;
+use zeroize::Zeroize;
 
 const ADLER32_MOD: u32 = 0xFFF1;
 
+/// Incremental [Adler-32](https://en.wikipedia.org/wiki/Adler-32) checksum algorithm.
+#[derive(Clone)]
+pub struct Adler32Checksum {
+    left: u32,
+    right: u32,
+}
+
+impl Adler32Checksum {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Checksum with developer defined left and right initialized values, both big endian.
+    #[inline(always)]
+    pub fn new(init: [u8; 8]) -> Self {
+        Adler32Checksum {
+            left: u32::from_be_bytes([init[0], init[1], init[2], init[3]]),
+            right: u32::from_be_bytes([init[4], init[5], init[6], init[7]]),
+        }
+    }
+}
+
+impl Drop for Adler32Checksum {
+    fn drop(&mut self) {
+        self.left.zeroize();
+        self.right.zeroize();
+    }
+}
+
+impl LicenseChecksumAlgorithm for Adler32Checksum {
+    #[inline(always)]
+    fn output_len(&self) -> usize {
+        4
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        // https://doc.rust-lang.org/stable/std/iter/trait.Iterator.html#method.fold
+        (self.left, self.right) =
+            data.iter()
+                .fold((self.left, self.right), |(left, right), &byte| {
+                    (
+                        left.wrapping_add(byte as u32) % ADLER32_MOD,
+                        right.wrapping_add(left + (byte) as u32) % ADLER32_MOD,
+                    )
+                });
+    }
+
+    #[inline(always)]
+    fn finalize(self: Box<Self>) -> Result<Vec<u8>> {
+        Ok(((self.right << 16) | self.left).to_be_bytes().to_vec())
+    }
+
+    #[inline(always)]
+    fn box_clone(&self) -> Box<dyn LicenseChecksumAlgorithm> {
+        Box::new(self.clone())
+    }
+}
+
 /// Checksum hash with [Adler-32](https://en.wikipedia.org/wiki/Adler-32)
 ///
 /// Generate checksum from hash with developer defined left and right initialized values.
 #[inline(always)]
 pub fn adler32_checksum(hash: &[u8], init: &[u8]) -> Result<Vec<u8>> {
-    if init.len() != 8 {
-        bail!(
-            "Cannot generate checksum with invalid init count! [count={}]",
-            init.len()
-        );
-    }
+    let init: [u8; 8] = init
+        .try_into()
+        .map_err(|_| LicenseError::InvalidChecksumInit {
+            expected: 8,
+            actual: init.len(),
+        })?;
 
-    let mut split = init.split_at(4);
-    let left_init = split.0.read_u32::<BigEndian>().unwrap();
-    let right_init = split.1.read_u32::<BigEndian>().unwrap();
-
-    // https://doc.rust-lang.org/stable/std/iter/trait.Iterator.html#method.fold
-    let (lo, hi) = hash
-        .iter()
-        .fold((left_init, right_init), |(left, right), &byte| {
-            (
-                left.wrapping_add(byte as u32) % ADLER32_MOD,
-                right.wrapping_add(left + (byte) as u32) % ADLER32_MOD,
-            )
-        });
-    Ok(((hi << 16) | lo).to_be_bytes().to_vec())
+    let mut algorithm = Box::new(Adler32Checksum::new(init));
+    algorithm.update(hash);
+    algorithm.finalize()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::adler32::adler32_checksum;
+    use crate::magic::prelude::*;
+    use crate::LicenseError;
 
     // Example test from [Adler-32](https://en.wikipedia.org/wiki/Adler-32) Wikipedia page
     #[test]
     fn validate_adler32_checksum() {
         let checksum = adler32_checksum(
-            &"Wikipedia".as_bytes().to_vec(),
+            "Wikipedia".as_bytes(),
             &Vec::from([0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]),
         );
 
         assert!(checksum.is_ok());
         assert_eq!(300286872_u32.to_be_bytes().to_vec(), checksum.unwrap())
     }
+
+    #[test]
+    fn validate_adler32_checksum_invalid_init() {
+        assert_eq!(
+            adler32_checksum("Wikipedia".as_bytes(), &[0x00, 0x01]),
+            Err(LicenseError::InvalidChecksumInit {
+                expected: 8,
+                actual: 2
+            })
+        );
+    }
 }
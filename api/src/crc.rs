@@ -0,0 +1,231 @@
+//! # CRC
+//!
+//! Cyclic redundancy check checksum algorithms
+//!
+//! [More about CRC](https://en.wikipedia.org/wiki/Cyclic_redundancy_check)
+
+use crate::license_checksum::LicenseChecksumAlgorithm;
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
+use crate::magic::Result;
+use zeroize::Zeroize;
+
+const CRC16_POLYNOMIAL: u16 = 0x1021;
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+const CRC16_TABLE: [u16; 256] = crc16_table();
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// Incremental CRC-16 (polynomial 0x1021, not reflected) checksum algorithm.
+#[derive(Clone)]
+pub struct Crc16Checksum {
+    crc: u16,
+}
+
+impl Crc16Checksum {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Checksum with developer defined initial value, initial value `FF FF` is
+    /// CRC-16/CCITT-FALSE.
+    #[inline(always)]
+    pub fn new(init: [u8; 2]) -> Self {
+        Crc16Checksum {
+            crc: u16::from_be_bytes(init),
+        }
+    }
+}
+
+impl Drop for Crc16Checksum {
+    fn drop(&mut self) {
+        self.crc.zeroize();
+    }
+}
+
+impl LicenseChecksumAlgorithm for Crc16Checksum {
+    #[inline(always)]
+    fn output_len(&self) -> usize {
+        2
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.crc = data.iter().fold(self.crc, |crc, &byte| {
+            (crc << 8) ^ CRC16_TABLE[((crc >> 8) as u8 ^ byte) as usize]
+        });
+    }
+
+    #[inline(always)]
+    fn finalize(self: Box<Self>) -> Result<Vec<u8>> {
+        Ok(self.crc.to_be_bytes().to_vec())
+    }
+
+    #[inline(always)]
+    fn box_clone(&self) -> Box<dyn LicenseChecksumAlgorithm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Incremental CRC-32 (polynomial 0x04C11DB7, reflected) checksum algorithm.
+#[derive(Clone)]
+pub struct Crc32Checksum {
+    crc: u32,
+}
+
+impl Crc32Checksum {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Checksum with developer defined initial value, initial value `FF FF FF FF` is CRC-32 used
+    /// by zlib.
+    #[inline(always)]
+    pub fn new(init: [u8; 4]) -> Self {
+        Crc32Checksum {
+            crc: u32::from_be_bytes(init),
+        }
+    }
+}
+
+impl Drop for Crc32Checksum {
+    fn drop(&mut self) {
+        self.crc.zeroize();
+    }
+}
+
+impl LicenseChecksumAlgorithm for Crc32Checksum {
+    #[inline(always)]
+    fn output_len(&self) -> usize {
+        4
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.crc = data.iter().fold(self.crc, |crc, &byte| {
+            (crc >> 8) ^ CRC32_TABLE[(crc as u8 ^ byte) as usize]
+        });
+    }
+
+    #[inline(always)]
+    fn finalize(self: Box<Self>) -> Result<Vec<u8>> {
+        Ok((!self.crc).to_be_bytes().to_vec())
+    }
+
+    #[inline(always)]
+    fn box_clone(&self) -> Box<dyn LicenseChecksumAlgorithm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Checksum hash with CRC-16 (polynomial 0x1021, not reflected)
+///
+/// Generate checksum from hash with developer defined initial value of 2 bytes, initial value
+/// `FF FF` is CRC-16/CCITT-FALSE.
+pub fn crc16_checksum(hash: &[u8], init: &[u8]) -> Result<Vec<u8>> {
+    let init: [u8; 2] = init
+        .try_into()
+        .map_err(|_| LicenseError::InvalidChecksumInit {
+            expected: 2,
+            actual: init.len(),
+        })?;
+
+    let mut algorithm = Box::new(Crc16Checksum::new(init));
+    algorithm.update(hash);
+    algorithm.finalize()
+}
+
+/// Checksum hash with CRC-32 (polynomial 0x04C11DB7, reflected)
+///
+/// Generate checksum from hash with developer defined initial value of 4 bytes, initial value
+/// `FF FF FF FF` is CRC-32 used by zlib.
+pub fn crc32_checksum(hash: &[u8], init: &[u8]) -> Result<Vec<u8>> {
+    let init: [u8; 4] = init
+        .try_into()
+        .map_err(|_| LicenseError::InvalidChecksumInit {
+            expected: 4,
+            actual: init.len(),
+        })?;
+
+    let mut algorithm = Box::new(Crc32Checksum::new(init));
+    algorithm.update(hash);
+    algorithm.finalize()
+}
+
+const fn crc16_table() -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = match crc & 0x8000 {
+                0 => crc << 1,
+                _ => (crc << 1) ^ CRC16_POLYNOMIAL,
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = match crc & 1 {
+                0 => crc >> 1,
+                _ => (crc >> 1) ^ CRC32_POLYNOMIAL,
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crc::{crc16_checksum, crc32_checksum};
+    use crate::magic::prelude::*;
+    use crate::LicenseError;
+
+    // Check values from [Catalogue of parametrised CRC algorithms](https://reveng.sourceforge.io/crc-catalogue/)
+    #[test]
+    fn validate_crc16_checksum() {
+        assert_eq!(
+            crc16_checksum("123456789".as_bytes(), &[0xFF, 0xFF]),
+            Ok(Vec::from([0x29, 0xB1]))
+        );
+        assert_eq!(
+            crc16_checksum("123456789".as_bytes(), &[0x00]),
+            Err(LicenseError::InvalidChecksumInit {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn validate_crc32_checksum() {
+        assert_eq!(
+            crc32_checksum("123456789".as_bytes(), &[0xFF, 0xFF, 0xFF, 0xFF]),
+            Ok(Vec::from([0xCB, 0xF4, 0x39, 0x26]))
+        );
+        assert_eq!(
+            crc32_checksum("".as_bytes(), &[0xFF, 0xFF, 0xFF, 0xFF]),
+            Ok(Vec::from([0x00, 0x00, 0x00, 0x00]))
+        );
+        assert_eq!(
+            crc32_checksum("123456789".as_bytes(), &[0; 8]),
+            Err(LicenseError::InvalidChecksumInit {
+                expected: 4,
+                actual: 8
+            })
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! # Keyed SHA3
+//!
+//! Keyed checksum truncating [SHA3-256](https://en.wikipedia.org/wiki/SHA-3) digest of secret key
+//! and hash. Unlike Adler-32 or CRC, checksum cannot be recomputed without the key.
+
+use crate::license_checksum::LicenseChecksumAlgorithm;
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
+use crate::magic::Result;
+use sha3::{Digest, Sha3_256};
+
+/// Size of truncated keyed SHA3 checksum.
+pub const KEYED_SHA3_SIZE: usize = 8;
+/// Minimal size of keyed SHA3 key.
+pub const KEYED_SHA3_MIN_KEY_SIZE: usize = 8;
+
+/// Incremental keyed SHA3 checksum algorithm, SHA3-256 of key size (2 bytes, big endian), key
+/// and hash truncated to [KEYED_SHA3_SIZE] bytes.
+///
+/// Key is not kept, only SHA3 state with absorbed key. `sha3` does not support zeroizing of its
+/// state, so the state is not wiped from memory on drop, key itself is wiped as magic of
+/// [LicenseChecksum](crate::license_checksum::LicenseChecksum).
+#[derive(Clone)]
+pub struct KeyedSha3Checksum {
+    hasher: Sha3_256,
+}
+
+impl KeyedSha3Checksum {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Checksum with developer defined secret key of at least [KEYED_SHA3_MIN_KEY_SIZE] bytes,
+    /// key is absorbed once and cloned state is reused for every checksum.
+    pub fn new(key: &[u8]) -> Result<Self> {
+        let key_size = match u16::try_from(key.len()) {
+            Ok(size) if key.len() >= KEYED_SHA3_MIN_KEY_SIZE => size,
+            _ => {
+                return Err(LicenseError::InvalidChecksumInit {
+                    expected: KEYED_SHA3_MIN_KEY_SIZE,
+                    actual: key.len(),
+                })
+            }
+        };
+
+        Ok(KeyedSha3Checksum {
+            hasher: Sha3_256::new()
+                .chain_update(key_size.to_be_bytes())
+                .chain_update(key),
+        })
+    }
+}
+
+impl LicenseChecksumAlgorithm for KeyedSha3Checksum {
+    #[inline(always)]
+    fn output_len(&self) -> usize {
+        KEYED_SHA3_SIZE
+    }
+
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(&mut self.hasher, data);
+    }
+
+    #[inline(always)]
+    fn finalize(self: Box<Self>) -> Result<Vec<u8>> {
+        Ok(self.hasher.finalize()[..KEYED_SHA3_SIZE].to_vec())
+    }
+
+    #[inline(always)]
+    fn box_clone(&self) -> Box<dyn LicenseChecksumAlgorithm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Checksum hash with SHA3-256 of key size (2 bytes, big endian), key and hash truncated to
+/// [KEYED_SHA3_SIZE] bytes.
+///
+/// Generate checksum from hash with developer defined secret key of at least
+/// [KEYED_SHA3_MIN_KEY_SIZE] bytes.
+pub fn keyed_sha3_checksum(hash: &[u8], key: &[u8]) -> Result<Vec<u8>> {
+    let mut algorithm = Box::new(KeyedSha3Checksum::new(key)?);
+    algorithm.update(hash);
+    algorithm.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keyed_sha3::keyed_sha3_checksum;
+    use crate::LicenseError;
+    use hex_literal::hex;
+
+    #[test]
+    fn validate_keyed_sha3_checksum() {
+        let key = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        assert_eq!(
+            keyed_sha3_checksum("123456789".as_bytes(), &key),
+            Ok(hex!("5637a73c09fff00c").to_vec())
+        );
+        assert_eq!(
+            keyed_sha3_checksum("".as_bytes(), &key),
+            Ok(hex!("0e0fcbbb12ca1ef8").to_vec())
+        );
+        assert_ne!(
+            keyed_sha3_checksum("123456789".as_bytes(), &[0; 8]),
+            keyed_sha3_checksum("123456789".as_bytes(), &key)
+        );
+        assert_eq!(
+            keyed_sha3_checksum("123456789".as_bytes(), &[0; 4]),
+            Err(LicenseError::InvalidChecksumInit {
+                expected: 8,
+                actual: 4
+            })
+        );
+    }
+}
@@ -1,18 +1,42 @@
 //! # Offline license rs
 //!
 //! **Offline license RS** license generator used for offline software license verification.
+//!
+//! Validation of license keys works under `no_std` + `alloc` with default `std` feature disabled,
+//! randomized magic, signing key generation, system clock and file access require `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
-extern crate core;
+extern crate alloc;
 
+pub use crate::license_error::LicenseError;
 pub use crate::license_key::LicenseKey;
 
 pub mod adler32;
+pub mod crc;
+pub mod keyed_sha3;
 pub mod license_blacklist;
+#[cfg(feature = "std")]
+pub mod license_bloom_blacklist;
 pub mod license_byte_check;
 pub mod license_checksum;
+#[cfg(feature = "config")]
+pub mod license_config;
+pub mod license_error;
+pub mod license_expiration;
+pub mod license_features;
 pub mod license_key;
+#[cfg(feature = "serde")]
+pub mod license_key_serde;
 pub mod license_magic;
 pub mod license_operator;
+pub mod license_operator_builder;
+pub mod license_operator_registry;
 pub mod license_properties;
+#[cfg(feature = "std")]
+pub mod license_revocation;
 pub mod license_serializer;
+pub mod license_signature;
+pub mod license_validation;
+pub mod license_verifier;
 mod magic;
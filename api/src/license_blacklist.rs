@@ -1,6 +1,131 @@
-#[derive(Default)]
+//! # License blacklist
+//!
+//! Blacklist of revoked license key seeds, which can be persisted in versioned binary file.
+//!
+//! File starts with header containing [BLACKLIST_FILE_MAGIC], version byte and count of entries,
+//! followed by entries. Every entry contains seed, revocation timestamp and reason, all numbers
+//! are big endian:
+//!
+//! | Field        | Size            |
+//! |--------------|-----------------|
+//! | seed size    | 2               |
+//! | seed         | seed size       |
+//! | revoked at   | 8, Unix seconds |
+//! | reason size  | 2               |
+//! | reason       | reason size     |
+//!
+//! Appended entry is committed by update of entry count in header, incomplete entry after last
+//! committed entry is ignored.
+
+#[cfg(feature = "std")]
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
+#[cfg(feature = "std")]
+use crate::magic::Result;
+
+#[cfg(feature = "std")]
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+#[cfg(feature = "std")]
+use std::fs::{File, OpenOptions};
+#[cfg(feature = "std")]
+use std::io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Magic bytes identifying blacklist file.
+pub const BLACKLIST_FILE_MAGIC: [u8; 4] = *b"OLBL";
+/// Current version of blacklist file format.
+pub const BLACKLIST_FILE_VERSION: u8 = 1;
+
+/// Offset of entry count in blacklist file header.
+#[cfg(feature = "std")]
+const BLACKLIST_COUNT_OFFSET: u64 = 5;
+#[cfg(feature = "std")]
+const BLACKLIST_HEADER_SIZE: u64 = 9;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct LicenseBlacklistEntry {
+    pub seed: Vec<u8>,
+    pub reason: String,
+    /// Time of revocation in seconds since Unix epoch.
+    pub revoked_at: u64,
+}
+
+impl LicenseBlacklistEntry {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Entry revoked at current system time, without `std` feature revocation time is 0.
+    pub fn new(seed: Vec<u8>, reason: &str) -> Self {
+        LicenseBlacklistEntry {
+            seed,
+            reason: reason.to_string(),
+            revoked_at: now(),
+        }
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    #[cfg(feature = "std")]
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        let seed_size = u16::try_from(self.seed.len()).map_err(invalid_blacklist)?;
+        let reason_size = u16::try_from(self.reason.len()).map_err(invalid_blacklist)?;
+
+        writer
+            .write_u16::<BigEndian>(seed_size)
+            .and_then(|_| writer.write_all(&self.seed))
+            .and_then(|_| writer.write_u64::<BigEndian>(self.revoked_at))
+            .and_then(|_| writer.write_u16::<BigEndian>(reason_size))
+            .and_then(|_| writer.write_all(self.reason.as_bytes()))
+            .map_err(invalid_blacklist)
+    }
+
+    #[cfg(feature = "std")]
+    fn read(reader: &mut impl Read) -> Result<Self> {
+        let seed_size = reader.read_u16::<BigEndian>().map_err(invalid_blacklist)?;
+        let mut seed = vec![0; seed_size as usize];
+        reader.read_exact(&mut seed).map_err(invalid_blacklist)?;
+
+        let revoked_at = reader.read_u64::<BigEndian>().map_err(invalid_blacklist)?;
+
+        let reason_size = reader.read_u16::<BigEndian>().map_err(invalid_blacklist)?;
+        let mut reason = vec![0; reason_size as usize];
+        reader.read_exact(&mut reason).map_err(invalid_blacklist)?;
+
+        Ok(LicenseBlacklistEntry {
+            seed,
+            reason: String::from_utf8(reason).map_err(invalid_blacklist)?,
+            revoked_at,
+        })
+    }
+}
+
+/// Backend of blacklist used by [LicenseOperator](crate::license_operator::LicenseOperator).
+///
+/// [LicenseBlacklist] keeps every revoked seed, compact backends like
+/// [BloomLicenseBlacklist](crate::license_bloom_blacklist::BloomLicenseBlacklist) trade exactness
+/// for size of very large revocation sets.
+pub trait LicenseBlacklistBackend {
+    fn is_blacklisted(&self, seed: &[u8]) -> bool;
+
+    fn push_entry(&mut self, entry: LicenseBlacklistEntry);
+
+    /// Revoked entries, backends which do not keep seeds return `None`.
+    fn entries(&self) -> Option<&Vec<LicenseBlacklistEntry>> {
+        None
+    }
+
+    fn box_clone(&self) -> Box<dyn LicenseBlacklistBackend>;
+}
+
+#[derive(Clone, Default)]
 pub struct LicenseBlacklist {
-    blacklist: Vec<Vec<u8>>,
+    entries: Vec<LicenseBlacklistEntry>,
 }
 
 impl LicenseBlacklist {
@@ -10,7 +135,38 @@ impl LicenseBlacklist {
 
     #[inline(always)]
     pub fn new(blacklist: Vec<Vec<u8>>) -> Self {
-        LicenseBlacklist { blacklist }
+        LicenseBlacklist {
+            entries: blacklist
+                .into_iter()
+                .map(|seed| LicenseBlacklistEntry {
+                    seed,
+                    reason: String::new(),
+                    revoked_at: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn from_entries(entries: Vec<LicenseBlacklistEntry>) -> Self {
+        LicenseBlacklist { entries }
+    }
+
+    /// Load blacklist from file, missing file is loaded as empty blacklist.
+    #[cfg(feature = "std")]
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => Self::from_bytes(&bytes),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(LicenseBlacklist::default()),
+            Err(error) => Err(invalid_blacklist(error)),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(LicenseBlacklist {
+            entries: read_committed_entries(&mut Cursor::new(bytes))?,
+        })
     }
 
     // ==================================================
@@ -19,12 +175,71 @@ impl LicenseBlacklist {
 
     #[inline(always)]
     pub fn push(&mut self, seed: Vec<u8>) {
-        self.blacklist.push(seed)
+        self.push_entry(LicenseBlacklistEntry::new(seed, ""))
     }
 
-    #[inline(always)]
-    pub fn is_blacklisted(&self, seed: Vec<u8>) -> bool {
-        self.blacklist.contains(&seed)
+    #[cfg(feature = "std")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let count = u32::try_from(self.entries.len()).map_err(invalid_blacklist)?;
+
+        let mut bytes = Vec::from(BLACKLIST_FILE_MAGIC);
+        bytes.push(BLACKLIST_FILE_VERSION);
+        bytes.extend(count.to_be_bytes());
+        for entry in &self.entries {
+            entry.write(&mut bytes)?;
+        }
+
+        Ok(bytes)
+    }
+
+    /// Save whole blacklist, existing file is replaced.
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_bytes()?).map_err(invalid_blacklist)
+    }
+
+    /// Append entry to blacklist file without rewriting existing entries, file is created when it
+    /// does not exist.
+    #[cfg(feature = "std")]
+    pub fn append_to_file(path: &Path, entry: &LicenseBlacklistEntry) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(invalid_blacklist)?;
+
+        let committed_entries = match file.metadata().map_err(invalid_blacklist)?.len() {
+            0 => {
+                LicenseBlacklist::default().write_to(&mut file)?;
+                Vec::new()
+            }
+            _ => read_committed_entries(&mut file)?,
+        };
+        let count = u32::try_from(committed_entries.len() + 1).map_err(invalid_blacklist)?;
+
+        // Drop incomplete entry after last committed entry
+        let committed_size = file.stream_position().map_err(invalid_blacklist)?;
+        file.set_len(committed_size).map_err(invalid_blacklist)?;
+
+        let mut bytes = Vec::new();
+        entry.write(&mut bytes)?;
+        file.write_all(&bytes).map_err(invalid_blacklist)?;
+
+        // Commit entry
+        file.seek(SeekFrom::Start(BLACKLIST_COUNT_OFFSET))
+            .and_then(|_| file.write_u32::<BigEndian>(count))
+            .and_then(|_| file.sync_all())
+            .map_err(invalid_blacklist)
+    }
+
+    #[cfg(feature = "std")]
+    fn write_to(&self, file: &mut File) -> Result<()> {
+        file.write_all(&self.to_bytes()?)
+            .and_then(|_| file.seek(SeekFrom::Start(BLACKLIST_HEADER_SIZE)))
+            .map(|_| ())
+            .map_err(invalid_blacklist)
     }
 
     // ==================================================
@@ -32,7 +247,182 @@ impl LicenseBlacklist {
     // ==================================================
 
     #[inline(always)]
-    pub fn get_blacklist(&self) -> &Vec<Vec<u8>> {
-        &self.blacklist
+    pub fn get_entries(&self) -> &Vec<LicenseBlacklistEntry> {
+        &self.entries
+    }
+
+    /// Blacklisted seeds without revocation reason and timestamp.
+    #[deprecated(note = "use `get_entries` returning revocation reason and timestamp of seeds")]
+    #[inline(always)]
+    pub fn get_blacklist(&self) -> Vec<Vec<u8>> {
+        self.entries
+            .iter()
+            .map(|entry| entry.seed.clone())
+            .collect()
+    }
+}
+
+impl LicenseBlacklistBackend for LicenseBlacklist {
+    #[inline(always)]
+    fn is_blacklisted(&self, seed: &[u8]) -> bool {
+        self.entries.iter().any(|entry| entry.seed == seed)
+    }
+
+    #[inline(always)]
+    fn push_entry(&mut self, entry: LicenseBlacklistEntry) {
+        self.entries.push(entry)
+    }
+
+    #[inline(always)]
+    fn entries(&self) -> Option<&Vec<LicenseBlacklistEntry>> {
+        Some(&self.entries)
+    }
+
+    #[inline(always)]
+    fn box_clone(&self) -> Box<dyn LicenseBlacklistBackend> {
+        Box::new(self.clone())
+    }
+}
+
+/// Read header and committed entries, reader is left after last committed entry.
+#[cfg(feature = "std")]
+fn read_committed_entries(reader: &mut impl Read) -> Result<Vec<LicenseBlacklistEntry>> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic).map_err(invalid_blacklist)?;
+    if magic != BLACKLIST_FILE_MAGIC {
+        return Err(LicenseError::InvalidBlacklist {
+            reason: "Missing blacklist file magic".to_string(),
+        });
+    }
+
+    let version = reader.read_u8().map_err(invalid_blacklist)?;
+    if version != BLACKLIST_FILE_VERSION {
+        return Err(LicenseError::InvalidBlacklist {
+            reason: format!("Unsupported blacklist file version {}", version),
+        });
+    }
+
+    let count = reader.read_u32::<BigEndian>().map_err(invalid_blacklist)?;
+
+    (0..count)
+        .map(|_| LicenseBlacklistEntry::read(reader))
+        .collect()
+}
+
+/// Current time in seconds since Unix epoch.
+#[cfg(feature = "std")]
+#[inline(always)]
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+fn now() -> u64 {
+    0
+}
+
+#[cfg(feature = "std")]
+#[inline(always)]
+fn invalid_blacklist<E: ToString>(error: E) -> LicenseError {
+    LicenseError::InvalidBlacklist {
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::license_blacklist::{
+        LicenseBlacklist, LicenseBlacklistBackend, LicenseBlacklistEntry,
+    };
+    use crate::LicenseError;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn blacklist_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "offline-license-{}-{}.blacklist",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn validate_blacklist_file_round_trip() {
+        let path = blacklist_path("round-trip");
+        let mut blacklist = LicenseBlacklist::default();
+        blacklist.push_entry(LicenseBlacklistEntry {
+            seed: Vec::from([0x01, 0x02, 0x03]),
+            reason: "Refunded".to_string(),
+            revoked_at: 1_672_531_200,
+        });
+        blacklist.push_entry(LicenseBlacklistEntry::new(Vec::from([0x04]), "Leaked"));
+
+        blacklist.save(&path).unwrap();
+        let loaded = LicenseBlacklist::load(&path).unwrap();
+
+        assert_eq!(loaded.get_entries(), blacklist.get_entries());
+        assert!(loaded.is_blacklisted(&[0x01, 0x02, 0x03]));
+        assert!(!loaded.is_blacklisted(&[0x01, 0x02]));
+        assert_eq!(
+            &blacklist.to_bytes().unwrap()[..9],
+            &[b'O', b'L', b'B', b'L', 1, 0, 0, 0, 2]
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn validate_blacklist_file_append() {
+        let path = blacklist_path("append");
+        let first = LicenseBlacklistEntry::new(Vec::from([0x01]), "Refunded");
+        let second = LicenseBlacklistEntry::new(Vec::from([0x02]), "Leaked");
+
+        // Missing file is empty blacklist created by first append
+        assert!(LicenseBlacklist::load(&path)
+            .unwrap()
+            .get_entries()
+            .is_empty());
+        LicenseBlacklist::append_to_file(&path, &first).unwrap();
+
+        // Incomplete entry of interrupted append is not committed
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[0x00, 0x05, 0xFF])
+            .unwrap();
+        assert_eq!(
+            LicenseBlacklist::load(&path).unwrap().get_entries(),
+            &Vec::from([first.clone()])
+        );
+
+        LicenseBlacklist::append_to_file(&path, &second).unwrap();
+        assert_eq!(
+            LicenseBlacklist::load(&path).unwrap().get_entries(),
+            &Vec::from([first, second])
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn validate_blacklist_file_invalid() {
+        assert!(matches!(
+            LicenseBlacklist::from_bytes(b"NOPE\x01\x00\x00\x00\x00"),
+            Err(LicenseError::InvalidBlacklist { .. })
+        ));
+        assert!(matches!(
+            LicenseBlacklist::from_bytes(b"OLBL\x02\x00\x00\x00\x00"),
+            Err(LicenseError::InvalidBlacklist { .. })
+        ));
+        // Entry count larger than entries
+        assert!(matches!(
+            LicenseBlacklist::from_bytes(b"OLBL\x01\x00\x00\x00\x01"),
+            Err(LicenseError::InvalidBlacklist { .. })
+        ));
     }
 }
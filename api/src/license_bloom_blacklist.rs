@@ -0,0 +1,222 @@
+//! # Bloom license blacklist
+//!
+//! Compact [blacklist backend](LicenseBlacklistBackend) based on
+//! [Bloom filter](https://en.wikipedia.org/wiki/Bloom_filter). Filter does not keep revoked seeds,
+//! so its size depends only on expected count of entries and false positive rate. Revoked seed is
+//! always reported as blacklisted, genuine seed is reported as blacklisted with false positive
+//! rate probability.
+//!
+//! Serialized filter contains big endian count of hash functions (4 bytes) and count of bits
+//! (8 bytes) followed by bits.
+
+use crate::license_blacklist::{LicenseBlacklist, LicenseBlacklistBackend, LicenseBlacklistEntry};
+use crate::license_error::LicenseError;
+use crate::magic::Result;
+
+use sha3::{digest::ExtendableOutput, Shake256};
+use std::f64::consts::LN_2;
+
+const BLOOM_HEADER_SIZE: usize = 12;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BloomLicenseBlacklist {
+    hash_count: u32,
+    bit_count: u64,
+    bits: Vec<u8>,
+}
+
+impl BloomLicenseBlacklist {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Empty filter sized for **expected_entries** with **false_positive_rate** between 0 and 1.
+    pub fn new(expected_entries: usize, false_positive_rate: f64) -> Result<Self> {
+        if expected_entries == 0 || !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(LicenseError::InvalidBlacklist {
+                reason: format!(
+                    "Cannot create Bloom filter with invalid parameters! [expected_entries={}, false_positive_rate={}]",
+                    expected_entries, false_positive_rate
+                ),
+            });
+        }
+
+        let entries = expected_entries as f64;
+        let bit_count = (-entries * false_positive_rate.ln() / (LN_2 * LN_2)).ceil() as u64;
+        let hash_count = ((bit_count as f64 / entries) * LN_2).round().max(1.0) as u32;
+
+        Ok(BloomLicenseBlacklist {
+            hash_count,
+            bit_count,
+            bits: vec![0; bit_count.div_ceil(8) as usize],
+        })
+    }
+
+    /// Filter containing every seed of exact **blacklist**.
+    pub fn from_blacklist(blacklist: &LicenseBlacklist, false_positive_rate: f64) -> Result<Self> {
+        let entries = blacklist.get_entries();
+        let mut filter = Self::new(entries.len().max(1), false_positive_rate)?;
+        for entry in entries {
+            filter.insert(&entry.seed);
+        }
+
+        Ok(filter)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < BLOOM_HEADER_SIZE {
+            return Err(LicenseError::InvalidBlacklist {
+                reason: format!(
+                    "Cannot read Bloom filter without header! [size={}]",
+                    bytes.len()
+                ),
+            });
+        }
+
+        let (header, bits) = bytes.split_at(BLOOM_HEADER_SIZE);
+        let hash_count = u32::from_be_bytes(header[..4].try_into().unwrap());
+        let bit_count = u64::from_be_bytes(header[4..].try_into().unwrap());
+
+        if hash_count == 0 || bit_count == 0 || bit_count.div_ceil(8) != bits.len() as u64 {
+            return Err(LicenseError::InvalidBlacklist {
+                reason: format!(
+                    "Cannot read Bloom filter with invalid header! [hash_count={}, bit_count={}, size={}]",
+                    hash_count,
+                    bit_count,
+                    bits.len()
+                ),
+            });
+        }
+
+        Ok(BloomLicenseBlacklist {
+            hash_count,
+            bit_count,
+            bits: bits.to_vec(),
+        })
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    pub fn insert(&mut self, seed: &[u8]) {
+        for index in self.bit_indexes(seed) {
+            self.bits[(index / 8) as usize] |= 1 << (index % 8);
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(BLOOM_HEADER_SIZE + self.bits.len());
+        bytes.extend(self.hash_count.to_be_bytes());
+        bytes.extend(self.bit_count.to_be_bytes());
+        bytes.extend(&self.bits);
+        bytes
+    }
+
+    /// Bit indexes of seed derived by double hashing of Shake256 digest.
+    fn bit_indexes(&self, seed: &[u8]) -> impl Iterator<Item = u64> {
+        let mut digest = [0; 16];
+        Shake256::digest_xof(seed, &mut digest);
+
+        let first = u64::from_be_bytes(digest[..8].try_into().unwrap());
+        let second = u64::from_be_bytes(digest[8..].try_into().unwrap());
+        let bit_count = self.bit_count;
+
+        (0..self.hash_count as u64)
+            .map(move |i| first.wrapping_add(i.wrapping_mul(second)) % bit_count)
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_hash_count(&self) -> u32 {
+        self.hash_count
+    }
+
+    #[inline(always)]
+    pub fn get_bit_count(&self) -> u64 {
+        self.bit_count
+    }
+}
+
+impl LicenseBlacklistBackend for BloomLicenseBlacklist {
+    #[inline(always)]
+    fn is_blacklisted(&self, seed: &[u8]) -> bool {
+        self.bit_indexes(seed)
+            .all(|index| self.bits[(index / 8) as usize] & (1 << (index % 8)) != 0)
+    }
+
+    #[inline(always)]
+    fn push_entry(&mut self, entry: LicenseBlacklistEntry) {
+        self.insert(&entry.seed)
+    }
+
+    #[inline(always)]
+    fn box_clone(&self) -> Box<dyn LicenseBlacklistBackend> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_blacklist::{LicenseBlacklist, LicenseBlacklistBackend};
+    use crate::license_bloom_blacklist::BloomLicenseBlacklist;
+    use crate::license_key::LicenseKeyStatus;
+    use crate::license_operator::LicenseOperator;
+    use crate::LicenseError;
+
+    #[test]
+    fn validate_bloom_blacklist_false_positive_rate() {
+        let revoked: Vec<Vec<u8>> = (0u32..10_000).map(|i| i.to_be_bytes().to_vec()).collect();
+        let filter =
+            BloomLicenseBlacklist::from_blacklist(&LicenseBlacklist::new(revoked.clone()), 0.01)
+                .unwrap();
+
+        // Roughly 9.6 bits and 7 hash functions per entry
+        assert_eq!(filter.get_hash_count(), 7);
+        assert_eq!(filter.get_bit_count(), 95_851);
+
+        // No false negatives
+        assert!(revoked.iter().all(|seed| filter.is_blacklisted(seed)));
+
+        let false_positives = (10_000u32..20_000)
+            .filter(|i| filter.is_blacklisted(&i.to_be_bytes()))
+            .count();
+        assert!(false_positives < 200, "false_positives={}", false_positives);
+
+        let restored = BloomLicenseBlacklist::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(restored, filter);
+
+        assert!(matches!(
+            BloomLicenseBlacklist::new(100, 1.0),
+            Err(LicenseError::InvalidBlacklist { .. })
+        ));
+        assert!(matches!(
+            BloomLicenseBlacklist::from_bytes(&filter.to_bytes()[..100]),
+            Err(LicenseError::InvalidBlacklist { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_bloom_blacklist_operator() {
+        let mut license_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+            .with_blacklist(Box::new(BloomLicenseBlacklist::new(1_000, 0.001).unwrap()));
+
+        let license_key = license_op
+            .generate_license_key("sample.name@sample.domain.com".as_bytes())
+            .unwrap();
+        assert_eq!(
+            license_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Valid
+        );
+
+        license_op.add_seed_to_blacklist(&license_key.seed);
+        assert!(license_op.get_blacklist().entries().is_none());
+        assert_eq!(
+            license_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Blacklisted
+        );
+    }
+}
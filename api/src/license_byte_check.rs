@@ -1,9 +1,12 @@
+use crate::license_error::LicenseError;
 use crate::license_magic::LicenseMagic;
 use crate::license_serializer::LicenseKeySerializer;
+use crate::magic::prelude::*;
 use crate::magic::Result;
-use simple_error::bail;
 
-#[derive(Default)]
+use subtle::{Choice, ConstantTimeEq};
+
+#[derive(Clone, Default)]
 pub struct LicenseByteCheck {
     byte_positions: Vec<usize>,
 }
@@ -14,13 +17,13 @@ impl LicenseByteCheck {
     // ==================================================
 
     pub fn new(byte_positions: Vec<usize>, magic: &LicenseMagic) -> Result<Self> {
-        for &i in byte_positions.iter() {
-            let magic_size = magic.get_magic().len();
-            if i > magic_size - 1 {
-                bail!(
-                    "Cannot initialize byte check with larger magic than {}!",
-                    magic_size
-                );
+        let magic_count = magic.payload_size();
+        for &position in byte_positions.iter() {
+            if position >= magic_count {
+                return Err(LicenseError::ByteCheckOutOfRange {
+                    position,
+                    magic_count,
+                });
             }
         }
 
@@ -36,6 +39,8 @@ impl LicenseByteCheck {
         self
     }
 
+    /// Validate payload bytes at every position, all positions are compared in constant time
+    /// without returning at first mismatching byte.
     pub fn validate(
         &self,
         payload: &[u8],
@@ -43,19 +48,131 @@ impl LicenseByteCheck {
         seed: &[u8],
         magic: &LicenseMagic,
     ) -> bool {
-        for &bc in &self.byte_positions {
-            match payload.get(bc) {
-                None => return false,
-                Some(&bs) => match magic.get_magic().get(bc) {
-                    None => return false,
-                    Some(m) => {
-                        if bs != serializer.hash(seed, m) {
-                            return false;
-                        }
-                    }
-                },
-            }
+        self.byte_positions
+            .iter()
+            .fold(Choice::from(1), |valid, &position| {
+                valid
+                    & Self::byte_matches(
+                        Self::expected_byte(position, serializer, seed, magic),
+                        payload.get(position).copied(),
+                    )
+            })
+            .into()
+    }
+
+    /// Compare expected and actual payload byte in constant time, missing byte never matches.
+    #[inline(always)]
+    pub fn byte_matches(expected: Option<u8>, actual: Option<u8>) -> Choice {
+        let present = Choice::from((expected.is_some() & actual.is_some()) as u8);
+        present
+            & expected
+                .unwrap_or_default()
+                .ct_eq(&actual.unwrap_or_default())
+    }
+
+    /// Payload byte expected at position, none when magic for position does not exist.
+    #[inline(always)]
+    pub fn expected_byte(
+        position: usize,
+        serializer: &dyn LicenseKeySerializer,
+        seed: &[u8],
+        magic: &LicenseMagic,
+    ) -> Option<u8> {
+        magic
+            .expose_secret()
+            .get(position)
+            .map(|m| serializer.hash(seed, m))
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_byte_positions(&self) -> &Vec<usize> {
+        &self.byte_positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_byte_check::LicenseByteCheck;
+    use crate::license_magic::LicenseMagic;
+    use crate::license_serializer::{DefaultLicenseKeySerializer, LicenseKeySerializer};
+    use crate::magic::prelude::*;
+    use crate::magic::Result;
+    use crate::LicenseError;
+    use core::cell::Cell;
+
+    /// Serializer counting computed payload bytes.
+    #[derive(Default)]
+    struct CountingSerializer {
+        count: Cell<usize>,
+    }
+
+    impl LicenseKeySerializer for CountingSerializer {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn hash(&self, seed: &[u8], magic: &[u8]) -> u8 {
+            self.count.set(self.count.get() + 1);
+            DefaultLicenseKeySerializer {}.hash(seed, magic)
+        }
+
+        fn deserialize_key(&self, key: String) -> Result<Vec<u8>> {
+            DefaultLicenseKeySerializer {}.deserialize_key(key)
+        }
+
+        fn serialize_key(&self, key: &[u8]) -> String {
+            DefaultLicenseKeySerializer {}.serialize_key(key)
+        }
+    }
+
+    #[test]
+    fn validate_byte_check_no_early_return() {
+        let magic = LicenseMagic::new((1..=4).map(|i| Vec::from([i * 2, i * 3, i * 5])).collect());
+        let byte_check = LicenseByteCheck::new(Vec::from([0, 1, 2, 3]), &magic).unwrap();
+        let seed = "sample.name@sample.domain.com".as_bytes();
+
+        let serializer = CountingSerializer::default();
+        let payload: Vec<u8> = magic
+            .expose_secret()
+            .iter()
+            .map(|m| serializer.hash(seed, m))
+            .collect();
+        serializer.count.set(0);
+
+        assert!(byte_check.validate(&payload, &serializer, seed, &magic));
+        assert_eq!(serializer.count.get(), 4);
+
+        // Every position is computed even when first byte does not match
+        for position in 0..4 {
+            let mut forged_payload = payload.clone();
+            forged_payload[position] ^= 0xFF;
+            serializer.count.set(0);
+
+            assert!(!byte_check.validate(&forged_payload, &serializer, seed, &magic));
+            assert_eq!(serializer.count.get(), 4);
         }
-        true
+
+        // Truncated payload is compared at every position too
+        serializer.count.set(0);
+        assert!(!byte_check.validate(&payload[..1], &serializer, seed, &magic));
+        assert_eq!(serializer.count.get(), 4);
+    }
+
+    #[test]
+    fn validate_byte_check_out_of_range() {
+        let magic = LicenseMagic::new(Vec::from([Vec::from([0x01]), Vec::from([0x02])]));
+
+        assert!(LicenseByteCheck::new(Vec::from([0, 1]), &magic).is_ok());
+        assert!(matches!(
+            LicenseByteCheck::new(Vec::from([0, 2]), &magic),
+            Err(LicenseError::ByteCheckOutOfRange {
+                position: 2,
+                magic_count: 2
+            })
+        ));
     }
 }
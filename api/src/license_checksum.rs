@@ -1,11 +1,120 @@
-use crate::adler32::adler32_checksum;
+use crate::adler32::Adler32Checksum;
+use crate::crc::{Crc16Checksum, Crc32Checksum};
+use crate::keyed_sha3::KeyedSha3Checksum;
+use crate::license_error::LicenseError;
 use crate::license_serializer::HashOperator;
+use crate::magic::prelude::*;
 use crate::magic::Result;
 
+use core::fmt::{Debug, Formatter};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Checksum algorithm computing checksum of license key incrementally.
+///
+/// Algorithm is configured once and cloned for every computed checksum, so it can hold
+/// precomputed tables or keys.
+pub trait LicenseChecksumAlgorithm {
+    /// Size of checksum returned by [finalize](LicenseChecksumAlgorithm::finalize).
+    fn output_len(&self) -> usize;
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self: Box<Self>) -> Result<Vec<u8>>;
+    fn box_clone(&self) -> Box<dyn LicenseChecksumAlgorithm>;
+}
+
+/// Factory of checksum algorithm configured with checksum magic, so checksum magic is the only
+/// source of algorithm configuration.
+pub type ChecksumFactory = fn(&[u8]) -> Result<Box<dyn LicenseChecksumAlgorithm>>;
+
+/// Adapter of [HashOperator] function to [LicenseChecksumAlgorithm], data are buffered until
+/// finalization.
+#[derive(Clone)]
+pub struct HashOperatorChecksum {
+    operator: HashOperator,
+    init: Vec<u8>,
+    output_len: usize,
+    data: Vec<u8>,
+}
+
+impl HashOperatorChecksum {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    #[inline(always)]
+    pub fn new(operator: HashOperator, init: Vec<u8>, output_len: usize) -> Self {
+        HashOperatorChecksum {
+            operator,
+            init,
+            output_len,
+            data: Vec::new(),
+        }
+    }
+}
+
+impl Drop for HashOperatorChecksum {
+    fn drop(&mut self) {
+        self.init.zeroize();
+        self.data.zeroize();
+    }
+}
+
+impl LicenseChecksumAlgorithm for HashOperatorChecksum {
+    #[inline(always)]
+    fn output_len(&self) -> usize {
+        self.output_len
+    }
+
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+
+    #[inline(always)]
+    fn finalize(self: Box<Self>) -> Result<Vec<u8>> {
+        (self.operator)(&self.data, &self.init)
+    }
+
+    #[inline(always)]
+    fn box_clone(&self) -> Box<dyn LicenseChecksumAlgorithm> {
+        Box::new(self.clone())
+    }
+}
+
+/// Checksum of license key, secret magic is wiped from memory on drop and redacted from [Debug]
+/// output.
 pub struct LicenseChecksum {
+    name: String,
     magic: Vec<u8>,
     byte_size: usize,
-    operator: HashOperator,
+    algorithm: Box<dyn LicenseChecksumAlgorithm>,
+}
+
+impl Clone for LicenseChecksum {
+    fn clone(&self) -> Self {
+        LicenseChecksum {
+            name: self.name.clone(),
+            magic: self.magic.clone(),
+            byte_size: self.byte_size,
+            algorithm: self.algorithm.box_clone(),
+        }
+    }
+}
+
+impl Debug for LicenseChecksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LicenseChecksum")
+            .field("name", &self.name)
+            .field("magic", &"[REDACTED]")
+            .field("byte_size", &self.byte_size)
+            .finish()
+    }
+}
+
+impl Drop for LicenseChecksum {
+    fn drop(&mut self) {
+        self.magic.zeroize();
+    }
 }
 
 impl LicenseChecksum {
@@ -13,39 +122,92 @@ impl LicenseChecksum {
     //                   Constructor
     // ==================================================
 
+    /// Checksum is named **custom**, use [with_name](LicenseChecksum::with_name) for checksum
+    /// operator registered in [LicenseRegistry](crate::license_config::LicenseRegistry).
+    #[inline(always)]
     pub fn new(magic: Vec<u8>, byte_size: usize, operator: HashOperator) -> Self {
-        LicenseChecksum {
-            magic,
-            byte_size,
-            operator,
-        }
+        let algorithm = HashOperatorChecksum::new(operator, magic.clone(), byte_size);
+        Self::from_parts(magic, Box::new(algorithm))
+    }
+
+    /// Checksum computed by algorithm created by **factory** from **magic**, checksum is named
+    /// **custom**. Algorithm is always derived from **magic**, so exported magic is the one used
+    /// for computing checksum.
+    pub fn with_algorithm(magic: Vec<u8>, factory: ChecksumFactory) -> Result<Self> {
+        let algorithm = factory(&magic)?;
+        Ok(Self::from_parts(magic, algorithm))
     }
 
     #[inline(always)]
     pub fn default(checksum_magic: [u8; 8]) -> Self {
+        Self::from_parts(
+            checksum_magic.to_vec(),
+            Box::new(Adler32Checksum::new(checksum_magic)),
+        )
+        .with_name("adler32")
+    }
+
+    /// CRC-16 checksum with initial value **init**.
+    #[inline(always)]
+    pub fn crc16(init: [u8; 2]) -> Self {
+        Self::from_parts(init.to_vec(), Box::new(Crc16Checksum::new(init))).with_name("crc16")
+    }
+
+    /// CRC-32 checksum with initial value **init**.
+    #[inline(always)]
+    pub fn crc32(init: [u8; 4]) -> Self {
+        Self::from_parts(init.to_vec(), Box::new(Crc32Checksum::new(init))).with_name("crc32")
+    }
+
+    /// Keyed SHA3 checksum with secret **key**, forged license key cannot compute valid checksum
+    /// without the key.
+    pub fn keyed_sha3(key: Vec<u8>) -> Result<Self> {
+        let algorithm = KeyedSha3Checksum::new(&key)?;
+        Ok(Self::from_parts(key, Box::new(algorithm)).with_name("keyed-sha3"))
+    }
+
+    /// Checksum of **algorithm** which has to be configured by **magic**.
+    #[inline(always)]
+    fn from_parts(magic: Vec<u8>, algorithm: Box<dyn LicenseChecksumAlgorithm>) -> Self {
         LicenseChecksum {
-            magic: checksum_magic.to_vec(),
-            byte_size: 4,
-            operator: adler32_checksum,
+            name: "custom".to_string(),
+            magic,
+            byte_size: algorithm.output_len(),
+            algorithm,
         }
     }
 
+    #[inline(always)]
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
     // ==================================================
     //                    Operators
     // ==================================================
 
-    #[inline(always)]
-    pub fn generate(&self, seed: &[u8]) -> Result<Vec<u8>> {
-        (self.operator)(seed, &self.magic)
-    }
+    /// Compute checksum of **data**, checksum has to have
+    /// [byte size](LicenseChecksum::get_byte_size) of algorithm.
+    pub fn generate(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut algorithm = self.algorithm.box_clone();
+        algorithm.update(data);
+        let checksum = algorithm.finalize()?;
 
-    pub fn validate(&self, key: Vec<u8>, payload: Vec<u8>, checksum: Vec<u8>) -> bool {
-        let mut bytes = Vec::new();
-        bytes.extend(key);
-        bytes.extend(payload);
+        if checksum.len() != self.byte_size {
+            return Err(LicenseError::InvalidChecksumSize {
+                expected: self.byte_size,
+                actual: checksum.len(),
+            });
+        }
 
-        match self.generate(&bytes) {
-            Ok(generated_checksum) => generated_checksum == checksum,
+        Ok(checksum)
+    }
+
+    /// Validate checksum of **data**, checksum is compared in constant time.
+    pub fn validate(&self, data: &[u8], checksum: &[u8]) -> bool {
+        match self.generate(data) {
+            Ok(generated_checksum) => generated_checksum.ct_eq(checksum).into(),
             Err(_) => false,
         }
     }
@@ -55,7 +217,13 @@ impl LicenseChecksum {
     // ==================================================
 
     #[inline(always)]
-    pub fn get_magic(&self) -> &Vec<u8> {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Raw secret magic, never log or display it.
+    #[inline(always)]
+    pub fn expose_secret(&self) -> &Vec<u8> {
         &self.magic
     }
 
@@ -63,4 +231,57 @@ impl LicenseChecksum {
     pub fn get_byte_size(&self) -> &usize {
         &self.byte_size
     }
+
+    #[inline(always)]
+    pub fn get_algorithm(&self) -> &dyn LicenseChecksumAlgorithm {
+        self.algorithm.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adler32::adler32_checksum;
+    use crate::crc::Crc16Checksum;
+    use crate::license_checksum::LicenseChecksum;
+    use crate::magic::prelude::*;
+    use crate::LicenseError;
+
+    #[test]
+    fn validate_license_checksum_hash_operator_adapter() {
+        let magic = [0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00];
+        let data = "Wikipedia".as_bytes();
+
+        let adapted = LicenseChecksum::new(magic.to_vec(), 4, adler32_checksum);
+        let native = LicenseChecksum::default(magic);
+        assert_eq!(adapted.generate(data), native.generate(data));
+        assert_eq!(
+            native.generate(data),
+            Ok(300286872_u32.to_be_bytes().to_vec())
+        );
+
+        // Algorithm is derived from magic
+        let crc = LicenseChecksum::with_algorithm(Vec::from([0x12, 0x34]), |magic| {
+            Ok(Box::new(Crc16Checksum::new(magic.try_into().map_err(
+                |_| LicenseError::InvalidChecksumInit {
+                    expected: 2,
+                    actual: magic.len(),
+                },
+            )?)))
+        })
+        .unwrap();
+        assert_eq!(
+            crc.generate(data),
+            LicenseChecksum::crc16([0x12, 0x34]).generate(data)
+        );
+        assert_eq!(crc.expose_secret(), &Vec::from([0x12, 0x34]));
+
+        // Byte size is tied to checksum returned by operator
+        assert_eq!(
+            LicenseChecksum::new(magic.to_vec(), 2, adler32_checksum).generate(data),
+            Err(LicenseError::InvalidChecksumSize {
+                expected: 2,
+                actual: 4
+            })
+        );
+    }
 }
@@ -0,0 +1,661 @@
+//! # License config
+//!
+//! Export and import of whole [LicenseOperator] configuration as TOML or JSON, so license key
+//! issuer and application validating license keys are configured from single source of truth.
+//!
+//! Serializer and checksum operator cannot be serialized, they are referenced by name registered
+//! in [LicenseRegistry]. Secrets are stored as hexadecimal strings, configuration of issuer has to
+//! be kept private.
+
+use crate::adler32::Adler32Checksum;
+use crate::crc::{Crc16Checksum, Crc32Checksum};
+use crate::keyed_sha3::KeyedSha3Checksum;
+use crate::license_blacklist::LicenseBlacklist;
+use crate::license_checksum::LicenseChecksum;
+use crate::license_error::LicenseError;
+use crate::license_expiration::SystemLicenseClock;
+use crate::license_features::LicenseFeatures;
+use crate::license_magic::LicenseMagic;
+use crate::license_operator::LicenseOperator;
+use crate::license_operator_builder::LicenseOperatorBuilder;
+use crate::license_properties::LicenseProperties;
+use crate::license_serializer::{
+    DefaultLicenseKeySerializer, HashOperator, HmacLicenseKeySerializer, LicenseKeySerializer,
+};
+use crate::license_signature::LicenseSignature;
+use crate::license_verifier::LicenseVerifier;
+use crate::magic::Result;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub type SerializerFactory = fn() -> Box<dyn LicenseKeySerializer>;
+pub use crate::license_checksum::ChecksumFactory;
+
+/// Checksum operator registered either as algorithm or as legacy [HashOperator] function.
+#[derive(Clone, Copy)]
+enum RegisteredChecksum {
+    Algorithm(ChecksumFactory),
+    Operator(HashOperator),
+}
+
+/// Registry of serializers and checksum operators referenced by name from configuration.
+pub struct LicenseRegistry {
+    serializers: BTreeMap<String, SerializerFactory>,
+    checksums: BTreeMap<String, RegisteredChecksum>,
+}
+
+impl Default for LicenseRegistry {
+    /// Registry with **default** and **hmac-sha3** serializers and **adler32**, **crc16**,
+    /// **crc32** and **keyed-sha3** checksum operators.
+    fn default() -> Self {
+        let mut registry = LicenseRegistry {
+            serializers: BTreeMap::new(),
+            checksums: BTreeMap::new(),
+        };
+
+        registry.register_serializer("default", || Box::new(DefaultLicenseKeySerializer {}));
+        registry.register_serializer("hmac-sha3", || Box::new(HmacLicenseKeySerializer {}));
+        registry.register_checksum_algorithm("adler32", |magic| {
+            Ok(Box::new(Adler32Checksum::new(checksum_init(magic)?)))
+        });
+        registry.register_checksum_algorithm("crc16", |magic| {
+            Ok(Box::new(Crc16Checksum::new(checksum_init(magic)?)))
+        });
+        registry.register_checksum_algorithm("crc32", |magic| {
+            Ok(Box::new(Crc32Checksum::new(checksum_init(magic)?)))
+        });
+        registry.register_checksum_algorithm("keyed-sha3", |magic| {
+            Ok(Box::new(KeyedSha3Checksum::new(magic)?))
+        });
+
+        registry
+    }
+}
+
+impl LicenseRegistry {
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    #[inline(always)]
+    pub fn register_serializer(&mut self, name: &str, factory: SerializerFactory) {
+        self.serializers.insert(name.to_string(), factory);
+    }
+
+    /// Register checksum operator function, its checksum size is taken from configuration.
+    #[inline(always)]
+    pub fn register_checksum(&mut self, name: &str, operator: HashOperator) {
+        self.checksums
+            .insert(name.to_string(), RegisteredChecksum::Operator(operator));
+    }
+
+    #[inline(always)]
+    pub fn register_checksum_algorithm(&mut self, name: &str, factory: ChecksumFactory) {
+        self.checksums
+            .insert(name.to_string(), RegisteredChecksum::Algorithm(factory));
+    }
+
+    pub fn create_serializer(&self, name: &str) -> Result<Box<dyn LicenseKeySerializer>> {
+        match self.serializers.get(name) {
+            Some(factory) => Ok(factory()),
+            None => Err(LicenseError::UnknownSerializer {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Create checksum registered as **name** configured with **magic**, checksum of registered
+    /// algorithm has to have **byte_size**.
+    pub fn create_checksum(
+        &self,
+        name: &str,
+        magic: Vec<u8>,
+        byte_size: usize,
+    ) -> Result<LicenseChecksum> {
+        let checksum = match self.checksums.get(name) {
+            Some(RegisteredChecksum::Algorithm(factory)) => {
+                LicenseChecksum::with_algorithm(magic, *factory)?
+            }
+            Some(&RegisteredChecksum::Operator(operator)) => {
+                LicenseChecksum::new(magic, byte_size, operator)
+            }
+            None => {
+                return Err(LicenseError::UnknownChecksum {
+                    name: name.to_string(),
+                })
+            }
+        };
+
+        if *checksum.get_byte_size() != byte_size {
+            return Err(LicenseError::InvalidChecksumSize {
+                expected: byte_size,
+                actual: *checksum.get_byte_size(),
+            });
+        }
+
+        Ok(checksum.with_name(name))
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct LicenseChecksumConfig {
+    pub name: String,
+    pub magic: String,
+    pub byte_size: usize,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct LicenseSignatureConfig {
+    /// Private key is present only in configuration of license key issuer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    pub public_key: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LicenseOperatorConfig {
+    pub properties: LicenseProperties,
+    pub magic: Vec<String>,
+    pub serializer: String,
+    pub checksum: LicenseChecksumConfig,
+    #[serde(default)]
+    pub byte_check: Vec<usize>,
+    #[serde(default)]
+    pub blacklist: Vec<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Expiration of license keys is validated against system clock.
+    #[serde(default)]
+    pub expiration: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<LicenseSignatureConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<u8>,
+}
+
+impl LicenseOperatorConfig {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Export configuration of **operator**, blacklist backend has to list its entries, e.g.
+    /// [BloomLicenseBlacklist](crate::license_bloom_blacklist::BloomLicenseBlacklist) cannot be
+    /// exported.
+    pub fn from_operator(operator: &LicenseOperator) -> Result<Self> {
+        let blacklist = operator.get_blacklist().entries().ok_or_else(|| {
+            invalid_config("Cannot export blacklist backend which does not list its entries!")
+        })?;
+
+        Ok(LicenseOperatorConfig {
+            properties: *operator.get_properties(),
+            magic: operator
+                .get_magic()
+                .expose_secret()
+                .iter()
+                .map(hex::encode)
+                .collect(),
+            serializer: operator.get_serializer().name().to_string(),
+            checksum: LicenseChecksumConfig {
+                name: operator.get_checksum().get_name().to_string(),
+                magic: hex::encode(operator.get_checksum().expose_secret()),
+                byte_size: *operator.get_checksum().get_byte_size(),
+            },
+            byte_check: operator.get_byte_check().get_byte_positions().clone(),
+            blacklist: blacklist
+                .iter()
+                .map(|entry| hex::encode(&entry.seed))
+                .collect(),
+            features: operator.get_features().get_names().clone(),
+            expiration: operator.has_expiration(),
+            generation: operator.get_generation(),
+            signature: operator
+                .get_signature()
+                .map(|signature| LicenseSignatureConfig {
+                    private_key: signature.get_private_key().map(hex::encode),
+                    public_key: hex::encode(signature.get_public_key()),
+                }),
+        })
+    }
+
+    /// Configuration of verifier contains only magic checked by verifier and public key.
+    #[inline(always)]
+    pub fn from_verifier(verifier: &LicenseVerifier) -> Result<Self> {
+        Self::from_operator(verifier.get_operator())
+    }
+
+    pub fn from_toml(config: &str) -> Result<Self> {
+        toml::from_str(config).map_err(invalid_config)
+    }
+
+    pub fn from_json(config: &str) -> Result<Self> {
+        serde_json::from_str(config).map_err(invalid_config)
+    }
+
+    /// Load configuration from file, files with **.json** extension are parsed as JSON and any
+    /// other file as TOML.
+    pub fn load(path: &Path) -> Result<Self> {
+        let config = fs::read_to_string(path).map_err(invalid_config)?;
+
+        match is_json(path) {
+            true => Self::from_json(&config),
+            false => Self::from_toml(&config),
+        }
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(invalid_config)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(invalid_config)
+    }
+
+    /// Save configuration to file, format is chosen by file extension same as in
+    /// [load](LicenseOperatorConfig::load).
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let config = match is_json(path) {
+            true => self.to_json()?,
+            false => self.to_toml()?,
+        };
+
+        fs::write(path, config).map_err(invalid_config)
+    }
+
+    /// Build license operator with serializer and checksum operator resolved from **registry**,
+    /// every magic has to be present.
+    #[inline(always)]
+    pub fn build(&self, registry: &LicenseRegistry) -> Result<LicenseOperator> {
+        self.builder(registry)?.build()
+    }
+
+    /// Build verifier from exported configuration of verifier, only magic checked by its byte
+    /// check has to be present.
+    #[inline(always)]
+    pub fn build_verifier(&self, registry: &LicenseRegistry) -> Result<LicenseVerifier> {
+        Ok(LicenseVerifier::new(
+            self.builder(registry)?.for_verifier().build()?,
+        ))
+    }
+
+    fn builder(&self, registry: &LicenseRegistry) -> Result<LicenseOperatorBuilder> {
+        let magic = LicenseMagic::new(
+            self.magic
+                .iter()
+                .map(|m| decode_hex(m))
+                .collect::<Result<_>>()?,
+        );
+
+        let checksum = registry.create_checksum(
+            &self.checksum.name,
+            decode_hex(&self.checksum.magic)?,
+            self.checksum.byte_size,
+        )?;
+
+        let blacklist = LicenseBlacklist::new(
+            self.blacklist
+                .iter()
+                .map(|seed| decode_hex(seed))
+                .collect::<Result<_>>()?,
+        );
+
+        let mut builder = LicenseOperator::builder()
+            .with_properties(self.properties)
+            .with_magic(magic)
+            .with_serializer(registry.create_serializer(&self.serializer)?)
+            .with_checksum(checksum)
+            .with_blacklist(Box::new(blacklist))
+            .with_byte_check(self.byte_check.clone())
+            .with_features(LicenseFeatures::new(self.features.clone())?);
+
+        if self.expiration {
+            builder = builder.with_expiration(Box::new(SystemLicenseClock {}));
+        }
+
+        if let Some(generation) = self.generation {
+            builder = builder.with_generation(generation);
+        }
+
+        if let Some(signature) = &self.signature {
+            let public_key = decode_key(&signature.public_key)?;
+            let license_signature = match &signature.private_key {
+                Some(private_key) => {
+                    let license_signature = LicenseSignature::signer(&decode_key(private_key)?);
+                    if license_signature.get_public_key() != public_key {
+                        return Err(LicenseError::InvalidPublicKey);
+                    }
+                    license_signature
+                }
+                None => LicenseSignature::verifier(&public_key)?,
+            };
+            builder = builder.with_signature(license_signature);
+        }
+
+        Ok(builder)
+    }
+}
+
+#[inline(always)]
+fn invalid_config<E: ToString>(error: E) -> LicenseError {
+    LicenseError::InvalidConfig {
+        reason: error.to_string(),
+    }
+}
+
+/// Initial value of fixed size checksum algorithm.
+fn checksum_init<const N: usize>(magic: &[u8]) -> Result<[u8; N]> {
+    magic
+        .try_into()
+        .map_err(|_| LicenseError::InvalidChecksumInit {
+            expected: N,
+            actual: magic.len(),
+        })
+}
+
+#[inline(always)]
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "json")
+}
+
+#[inline(always)]
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value).map_err(invalid_config)
+}
+
+fn decode_key(value: &str) -> Result<[u8; 32]> {
+    let key = decode_hex(value)?;
+
+    key.try_into()
+        .map_err(|key: Vec<u8>| LicenseError::InvalidConfig {
+            reason: format!("Ed25519 key has to be 32 bytes long! [size={}]", key.len()),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_blacklist::LicenseBlacklist;
+    use crate::license_bloom_blacklist::BloomLicenseBlacklist;
+    use crate::license_byte_check::LicenseByteCheck;
+    use crate::license_checksum::LicenseChecksum;
+    use crate::license_config::{LicenseOperatorConfig, LicenseRegistry};
+    use crate::license_features::LicenseFeatures;
+    use crate::license_key::{LicenseKeyOptions, LicenseKeyStatus};
+    use crate::license_magic::LicenseMagic;
+    use crate::license_operator::LicenseOperator;
+    use crate::license_properties::LicenseProperties;
+    use crate::license_serializer::DefaultLicenseKeySerializer;
+    use crate::license_signature::LicenseSignature;
+    use crate::LicenseError;
+
+    fn issuer_operator() -> LicenseOperator {
+        let magic = LicenseMagic::new(Vec::from([
+            Vec::from([0xFF, 0xAA, 0x12]),
+            Vec::from([0x45, 0x5A, 0xAD]),
+            Vec::from([0x1F, 0x11, 0xA8]),
+        ]));
+        let byte_check = LicenseByteCheck::new(Vec::from([0, 2]), &magic).unwrap();
+
+        let mut operator = LicenseOperator::new(
+            LicenseProperties {
+                key_size: 24,
+                magic_size: 3,
+                magic_count: 3,
+            },
+            magic,
+            Box::new(DefaultLicenseKeySerializer {}),
+            LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]),
+            LicenseBlacklist::default(),
+            byte_check,
+        )
+        .with_features(LicenseFeatures::new(Vec::from(["export_pdf".to_string()])).unwrap())
+        .with_signature(LicenseSignature::signer(&[7; 32]));
+        operator.add_seed_to_blacklist(&[0x01, 0x02, 0x03]);
+
+        operator
+    }
+
+    #[test]
+    fn validate_license_config_round_trip() {
+        let user_email = "sample.name@sample.domain.com";
+        let issuer_op = issuer_operator();
+        let registry = LicenseRegistry::default();
+
+        let options = LicenseKeyOptions {
+            features: Vec::from(["export_pdf".to_string()]),
+            ..Default::default()
+        };
+        let license_key = issuer_op
+            .generate_license_key_with_options(user_email.as_bytes(), &options)
+            .unwrap();
+        let serialized_key = issuer_op.get_serialized_key(&license_key);
+
+        let config = LicenseOperatorConfig::from_operator(&issuer_op).unwrap();
+        let toml_config = config.to_toml().unwrap();
+        let json_config = config.to_json().unwrap();
+
+        for imported in [
+            LicenseOperatorConfig::from_toml(&toml_config).unwrap(),
+            LicenseOperatorConfig::from_json(&json_config).unwrap(),
+        ] {
+            assert_eq!(imported.magic, config.magic);
+            assert_eq!(imported.checksum, config.checksum);
+            assert_eq!(imported.blacklist, Vec::from(["010203".to_string()]));
+
+            let imported_op = imported.build(&registry).unwrap();
+            let parsed_key = imported_op.parse_key(&serialized_key).unwrap();
+
+            assert_eq!(
+                imported_op.validate_license_key(&parsed_key),
+                LicenseKeyStatus::Valid
+            );
+            assert!(parsed_key.has_feature("export_pdf"));
+            assert!(imported_op
+                .generate_license_key_with_options(user_email.as_bytes(), &options)
+                .is_ok());
+        }
+
+        // Verifier configuration without private key
+        let mut verifier_config = config.clone();
+        verifier_config.signature.as_mut().unwrap().private_key = None;
+        let verifier_op = LicenseOperatorConfig::from_toml(&verifier_config.to_toml().unwrap())
+            .unwrap()
+            .build(&registry)
+            .unwrap();
+
+        assert_eq!(
+            verifier_op.validate_license_key(&verifier_op.parse_key(&serialized_key).unwrap()),
+            LicenseKeyStatus::Valid
+        );
+        assert_eq!(
+            verifier_op.generate_license_key_with_options(user_email.as_bytes(), &options),
+            Err(LicenseError::MissingSigningKey)
+        );
+    }
+
+    #[test]
+    fn validate_license_config_registry() {
+        let mut config = LicenseOperatorConfig::from_operator(&issuer_operator()).unwrap();
+        config.serializer = "unknown".to_string();
+
+        assert!(matches!(
+            config.build(&LicenseRegistry::default()),
+            Err(LicenseError::UnknownSerializer { .. })
+        ));
+
+        let mut config = LicenseOperatorConfig::from_operator(&issuer_operator()).unwrap();
+        config.checksum.name = "custom".to_string();
+
+        assert!(matches!(
+            config.build(&LicenseRegistry::default()),
+            Err(LicenseError::UnknownChecksum { .. })
+        ));
+
+        let mut registry = LicenseRegistry::default();
+        registry.register_checksum("custom", crate::adler32::adler32_checksum);
+        assert!(config.build(&registry).is_ok());
+
+        assert!(matches!(
+            LicenseOperatorConfig::from_toml("properties = 1"),
+            Err(LicenseError::InvalidConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_license_config_builder() {
+        let mut config = LicenseOperatorConfig::from_operator(&issuer_operator()).unwrap();
+        config.magic[1] = "455a".to_string();
+
+        // Configuration is validated by operator builder
+        assert_eq!(
+            config.build(&LicenseRegistry::default()).err(),
+            Some(LicenseError::MagicLengthMismatch {
+                position: 1,
+                expected: 3,
+                actual: 2
+            })
+        );
+
+        // Issuer without magic would generate constant payload
+        let mut config = LicenseOperatorConfig::from_operator(&issuer_operator()).unwrap();
+        config.magic = Vec::from(["".to_string(), "".to_string(), "".to_string()]);
+
+        assert!(matches!(
+            config.build(&LicenseRegistry::default()),
+            Err(LicenseError::MagicLengthMismatch { position: 0, .. })
+        ));
+
+        let mut config = LicenseOperatorConfig::from_operator(&issuer_operator()).unwrap();
+        config.byte_check = Vec::from([3]);
+
+        assert_eq!(
+            config.build(&LicenseRegistry::default()).err(),
+            Some(LicenseError::ByteCheckOutOfRange {
+                position: 3,
+                magic_count: 3
+            })
+        );
+    }
+
+    #[test]
+    fn validate_license_config_bloom_blacklist() {
+        let filter = BloomLicenseBlacklist::new(10, 0.01).unwrap();
+        let operator = issuer_operator().with_blacklist(Box::new(filter));
+
+        // Filter does not list revoked seeds, exported verifier would accept them
+        assert!(matches!(
+            LicenseOperatorConfig::from_operator(&operator),
+            Err(LicenseError::InvalidConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_license_config_checksums() {
+        let user_email = "sample.name@sample.domain.com";
+        let registry = LicenseRegistry::default();
+
+        for checksum in [
+            LicenseChecksum::crc16([0xFF, 0xFF]),
+            LicenseChecksum::crc32([0xFF, 0xFF, 0xFF, 0xFF]),
+            LicenseChecksum::keyed_sha3(Vec::from([9; 16])).unwrap(),
+        ] {
+            let magic = LicenseMagic::new(Vec::from([Vec::from([0xFF, 0xAA, 0x12])]));
+            let issuer_op = LicenseOperator::new(
+                LicenseProperties {
+                    key_size: 24,
+                    magic_size: 1,
+                    magic_count: 3,
+                },
+                magic,
+                Box::new(DefaultLicenseKeySerializer {}),
+                checksum,
+                LicenseBlacklist::default(),
+                LicenseByteCheck::default(),
+            );
+            let license_key = issuer_op
+                .generate_license_key(user_email.as_bytes())
+                .unwrap();
+
+            let config = LicenseOperatorConfig::from_toml(
+                &LicenseOperatorConfig::from_operator(&issuer_op)
+                    .unwrap()
+                    .to_toml()
+                    .unwrap(),
+            )
+            .unwrap();
+            let imported_op = config.build(&registry).unwrap();
+            let parsed_key = imported_op
+                .parse_key(&issuer_op.get_serialized_key(&license_key))
+                .unwrap();
+
+            assert_eq!(
+                imported_op.validate_license_key(&parsed_key),
+                LicenseKeyStatus::Valid,
+                "checksum={}",
+                config.checksum.name
+            );
+        }
+    }
+
+    #[test]
+    fn validate_license_config_verifier() {
+        let issuer_op = issuer_operator();
+        let license_key = issuer_op
+            .generate_license_key("sample.name@sample.domain.com".as_bytes())
+            .unwrap();
+
+        let verifier = issuer_op
+            .derive_verifier(LicenseByteCheck::new(Vec::from([1]), issuer_op.get_magic()).unwrap());
+        let config = LicenseOperatorConfig::from_verifier(&verifier).unwrap();
+
+        assert_eq!(
+            config.magic,
+            Vec::from(["".to_string(), "455aad".to_string(), "".to_string()])
+        );
+        assert_eq!(config.signature.as_ref().unwrap().private_key, None);
+
+        // Exported verifier contains no secret of issuer
+        let issuer_toml = LicenseOperatorConfig::from_operator(&issuer_op)
+            .unwrap()
+            .to_toml()
+            .unwrap();
+        let verifier_toml = config.to_toml().unwrap();
+        for secret in ["ffaa12", "1f11a8", &hex::encode([7; 32])] {
+            assert!(issuer_toml.contains(secret));
+            assert!(!verifier_toml.contains(secret), "secret={}", secret);
+        }
+
+        // Verifier configuration cannot build issuer
+        assert!(matches!(
+            config.build(&LicenseRegistry::default()),
+            Err(LicenseError::MagicLengthMismatch { position: 0, .. })
+        ));
+
+        // Byte check of verifier needs its magic
+        let mut unchecked_config = config.clone();
+        unchecked_config.byte_check = Vec::from([0, 1]);
+        assert!(matches!(
+            unchecked_config.build_verifier(&LicenseRegistry::default()),
+            Err(LicenseError::MagicLengthMismatch { position: 0, .. })
+        ));
+
+        let imported = LicenseOperatorConfig::from_toml(&config.to_toml().unwrap())
+            .unwrap()
+            .build_verifier(&LicenseRegistry::default())
+            .unwrap();
+        let parsed_key = imported
+            .parse_key(&issuer_op.get_serialized_key(&license_key))
+            .unwrap();
+
+        assert_eq!(
+            imported.validate_license_key(&parsed_key),
+            LicenseKeyStatus::Valid
+        );
+    }
+}
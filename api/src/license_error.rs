@@ -0,0 +1,214 @@
+use crate::magic::prelude::*;
+use core::fmt::{Display, Formatter};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum LicenseError {
+    /// Checksum operator received initialization magic with unexpected size.
+    InvalidChecksumInit { expected: usize, actual: usize },
+    /// Checksum operator produced checksum with different size than defined by license checksum.
+    InvalidChecksumSize { expected: usize, actual: usize },
+    /// Byte check position points outside of license magic.
+    ByteCheckOutOfRange { position: usize, magic_count: usize },
+    /// License key properties cannot fit into configured key size.
+    InsufficientKeySize { required: usize, key_size: usize },
+    /// Serialized license key is shorter than its properties.
+    KeyTooShort { required: usize, actual: usize },
+    /// Parsed license key does not match size of license key defined by operator.
+    MalformedKey { expected: usize, actual: usize },
+    /// License key could not be decoded by serializer.
+    InvalidKeyEncoding { reason: String },
+    /// Signing of license key requires private key.
+    MissingSigningKey,
+    /// Public key is not valid Ed25519 verifying key.
+    InvalidPublicKey,
+    /// Date is not valid calendar date or is out of range of license date.
+    InvalidDate { year: i64, month: u32, day: u32 },
+    /// Date is not formatted as `YYYY-MM-DD`.
+    InvalidDateFormat { date: String },
+    /// License expires before it was issued, dates are in days since Unix epoch.
+    InvalidExpiration { issued: u16, expires: u16 },
+    /// Operator with expiration requires expiration of generated license key.
+    MissingExpiration,
+    /// Operator without expiration cannot generate license key with expiration.
+    ExpirationNotSupported,
+    /// Feature is not defined in feature schema.
+    UnknownFeature { name: String },
+    /// Feature is defined more than once in feature schema.
+    DuplicateFeature { name: String },
+    /// Serializer is not registered in license registry.
+    UnknownSerializer { name: String },
+    /// Checksum operator is not registered in license registry.
+    UnknownChecksum { name: String },
+    /// License operator configuration cannot be read, written or parsed.
+    InvalidConfig { reason: String },
+    /// Blacklist file cannot be read, written or parsed.
+    InvalidBlacklist { reason: String },
+    /// Revocation list cannot be parsed or its signature is not valid.
+    InvalidRevocationList { reason: String },
+    /// Revocation list is older than revocation list already loaded by verifier.
+    OutdatedRevocationList { version: u64, current: u64 },
+    /// Operator registered in operator registry requires generation.
+    MissingGeneration,
+    /// Generation is registered in operator registry more than once.
+    DuplicateGeneration { generation: u8 },
+    /// License key generation is not registered in operator registry.
+    UnknownGeneration { generation: u8 },
+    /// License operator builder is missing required component.
+    MissingOperatorComponent { component: String },
+    /// Count of magic differs from magic size of license properties.
+    MagicSizeMismatch { expected: usize, actual: usize },
+    /// Size of magic at position differs from magic count of license properties.
+    MagicLengthMismatch {
+        position: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl Display for LicenseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LicenseError::InvalidChecksumInit { expected, actual } => write!(
+                f,
+                "Cannot generate checksum with invalid init count! [expected={}, count={}]",
+                expected, actual
+            ),
+            LicenseError::InvalidChecksumSize { expected, actual } => write!(
+                f,
+                "Cannot generate license key with different checksum size than defined! [expected={}, actual={}]",
+                expected, actual
+            ),
+            LicenseError::ByteCheckOutOfRange {
+                position,
+                magic_count,
+            } => write!(
+                f,
+                "Cannot initialize byte check with larger position than magic count! [position={}, magic_count={}]",
+                position, magic_count
+            ),
+            LicenseError::InsufficientKeySize { required, key_size } => write!(
+                f,
+                "Cannot generate license key with less than {} key size! [key_size={}]",
+                required, key_size
+            ),
+            LicenseError::KeyTooShort { required, actual } => write!(
+                f,
+                "Cannot deserialize license key with larger properties than raw key itself! [required={}, actual={}]",
+                required, actual
+            ),
+            LicenseError::MalformedKey { expected, actual } => write!(
+                f,
+                "Cannot parse license key with invalid size! [expected={}, actual={}]",
+                expected, actual
+            ),
+            LicenseError::InvalidKeyEncoding { reason } => {
+                write!(f, "Cannot decode license key! [reason={}]", reason)
+            }
+            LicenseError::MissingSigningKey => {
+                write!(f, "Cannot sign license key without private key!")
+            }
+            LicenseError::InvalidPublicKey => {
+                write!(f, "Cannot verify license key with invalid public key!")
+            }
+            LicenseError::InvalidDate { year, month, day } => write!(
+                f,
+                "Cannot create license date from invalid date! [date={}-{:02}-{:02}]",
+                year, month, day
+            ),
+            LicenseError::InvalidDateFormat { date } => write!(
+                f,
+                "Cannot parse date in YYYY-MM-DD format! [date={}]",
+                date
+            ),
+            LicenseError::InvalidExpiration { issued, expires } => write!(
+                f,
+                "Cannot create license expiration expiring before issue! [issued={}, expires={}]",
+                issued, expires
+            ),
+            LicenseError::MissingExpiration => {
+                write!(f, "Cannot generate license key without expiration!")
+            }
+            LicenseError::ExpirationNotSupported => write!(
+                f,
+                "Cannot generate license key with expiration by operator without expiration!"
+            ),
+            LicenseError::UnknownFeature { name } => {
+                write!(f, "Cannot enable undefined license feature! [name={}]", name)
+            }
+            LicenseError::DuplicateFeature { name } => write!(
+                f,
+                "Cannot define license feature more than once! [name={}]",
+                name
+            ),
+            LicenseError::UnknownSerializer { name } => {
+                write!(f, "Cannot find registered serializer! [name={}]", name)
+            }
+            LicenseError::UnknownChecksum { name } => {
+                write!(f, "Cannot find registered checksum! [name={}]", name)
+            }
+            LicenseError::InvalidConfig { reason } => write!(
+                f,
+                "Cannot process license operator configuration! [reason={}]",
+                reason
+            ),
+            LicenseError::InvalidBlacklist { reason } => write!(
+                f,
+                "Cannot process license blacklist file! [reason={}]",
+                reason
+            ),
+            LicenseError::InvalidRevocationList { reason } => write!(
+                f,
+                "Cannot load license revocation list! [reason={}]",
+                reason
+            ),
+            LicenseError::MissingGeneration => {
+                write!(f, "Cannot register license operator without generation!")
+            }
+            LicenseError::DuplicateGeneration { generation } => write!(
+                f,
+                "Cannot register license operator generation twice! [generation={}]",
+                generation
+            ),
+            LicenseError::UnknownGeneration { generation } => write!(
+                f,
+                "Cannot find license operator of license key generation! [generation={}]",
+                generation
+            ),
+            LicenseError::OutdatedRevocationList { version, current } => write!(
+                f,
+                "Cannot load revocation list older than loaded revocation list! [version={}, current={}]",
+                version, current
+            ),
+            LicenseError::MissingOperatorComponent { component } => write!(
+                f,
+                "Cannot build license operator without required component! [component={}]",
+                component
+            ),
+            LicenseError::MagicSizeMismatch { expected, actual } => write!(
+                f,
+                "Cannot build license operator with different count of magic than magic size! [expected={}, actual={}]",
+                expected, actual
+            ),
+            LicenseError::MagicLengthMismatch {
+                position,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Cannot build license operator with different magic length than magic count! [position={}, expected={}, actual={}]",
+                position, expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LicenseError {}
+
+impl From<hex::FromHexError> for LicenseError {
+    fn from(error: hex::FromHexError) -> Self {
+        LicenseError::InvalidKeyEncoding {
+            reason: error.to_string(),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! # License expiration
+//!
+//! Issue and expiry date of license key packed into 4 bytes, every date is stored as count of days
+//! since Unix epoch in 2 bytes (1970-01-01 up to 2149-06-06).
+
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
+use crate::magic::Result;
+
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Size of expiration section in license key.
+pub const EXPIRATION_SIZE: usize = 4;
+
+#[cfg(feature = "std")]
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug, Default)]
+pub struct LicenseDate {
+    days: u16,
+}
+
+impl LicenseDate {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    #[inline(always)]
+    pub fn from_days(days: u16) -> Self {
+        LicenseDate { days }
+    }
+
+    /// Create date from proleptic Gregorian calendar date.
+    pub fn from_ymd(year: i64, month: u32, day: u32) -> Result<Self> {
+        let invalid_date = LicenseError::InvalidDate { year, month, day };
+
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(invalid_date);
+        }
+        // Years outside of license date range would overflow day arithmetic
+        if !(1969..=2150).contains(&year) {
+            return Err(invalid_date);
+        }
+
+        // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let y = if month <= 2 { year - 1 } else { year };
+        let era = y.div_euclid(400);
+        let yoe = y.rem_euclid(400);
+        let m = month as i64;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468;
+
+        match u16::try_from(days) {
+            // Reject overflowing days of month, e.g. 31st of February
+            Ok(days) if LicenseDate::from_days(days).to_ymd() == (year, month, day) => {
+                Ok(LicenseDate::from_days(days))
+            }
+            _ => Err(invalid_date),
+        }
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    /// Convert date into proleptic Gregorian calendar year, month and day.
+    pub fn to_ymd(&self) -> (i64, u32, u32) {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = self.days as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        (year, month, day)
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_days(&self) -> u16 {
+        self.days
+    }
+}
+
+impl Display for LicenseDate {
+    /// Date formatted as `YYYY-MM-DD`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let (year, month, day) = self.to_ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl FromStr for LicenseDate {
+    type Err = LicenseError;
+
+    /// Parse date formatted as `YYYY-MM-DD`.
+    fn from_str(date: &str) -> Result<Self> {
+        let invalid_format = || LicenseError::InvalidDateFormat {
+            date: date.to_string(),
+        };
+
+        let mut parts = date.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid_format());
+        };
+
+        LicenseDate::from_ymd(
+            year.parse().map_err(|_| invalid_format())?,
+            month.parse().map_err(|_| invalid_format())?,
+            day.parse().map_err(|_| invalid_format())?,
+        )
+    }
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct LicenseExpiration {
+    pub issued: LicenseDate,
+    pub expires: LicenseDate,
+}
+
+impl LicenseExpiration {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    pub fn new(issued: LicenseDate, expires: LicenseDate) -> Result<Self> {
+        if expires < issued {
+            return Err(LicenseError::InvalidExpiration {
+                issued: issued.get_days(),
+                expires: expires.get_days(),
+            });
+        }
+
+        Ok(LicenseExpiration { issued, expires })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != EXPIRATION_SIZE {
+            return Err(LicenseError::MalformedKey {
+                expected: EXPIRATION_SIZE,
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(LicenseExpiration {
+            issued: LicenseDate::from_days(u16::from_be_bytes([bytes[0], bytes[1]])),
+            expires: LicenseDate::from_days(u16::from_be_bytes([bytes[2], bytes[3]])),
+        })
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    pub fn to_bytes(&self) -> [u8; EXPIRATION_SIZE] {
+        let issued = self.issued.get_days().to_be_bytes();
+        let expires = self.expires.get_days().to_be_bytes();
+
+        [issued[0], issued[1], expires[0], expires[1]]
+    }
+
+    /// License is expired after its expiry date and before its issue date, both dates are still
+    /// valid. License key issued in future is rejected, so key cannot be issued ahead of time.
+    #[inline(always)]
+    pub fn is_expired(&self, today: LicenseDate) -> bool {
+        today < self.issued || today > self.expires
+    }
+}
+
+/// Source of current date for validation of license key expiration.
+pub trait LicenseClock {
+    fn today(&self) -> LicenseDate;
+}
+
+/// Clock reading current date from system time.
+#[cfg(feature = "std")]
+pub struct SystemLicenseClock {}
+
+#[cfg(feature = "std")]
+impl LicenseClock for SystemLicenseClock {
+    fn today(&self) -> LicenseDate {
+        let days = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() / SECONDS_PER_DAY,
+            Err(_) => 0,
+        };
+
+        LicenseDate::from_days(u16::try_from(days).unwrap_or(u16::MAX))
+    }
+}
+
+/// Clock always returning same date, useful for testing.
+pub struct FixedLicenseClock {
+    pub today: LicenseDate,
+}
+
+impl LicenseClock for FixedLicenseClock {
+    #[inline(always)]
+    fn today(&self) -> LicenseDate {
+        self.today
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_expiration::{LicenseDate, LicenseExpiration};
+    use crate::magic::prelude::*;
+    use crate::LicenseError;
+
+    #[test]
+    fn validate_license_date_conversion() {
+        assert_eq!(LicenseDate::from_ymd(1970, 1, 1).unwrap().get_days(), 0);
+        assert_eq!(
+            LicenseDate::from_ymd(2000, 3, 1).unwrap().get_days(),
+            11_017
+        );
+        assert_eq!(
+            LicenseDate::from_ymd(2149, 6, 6).unwrap().get_days(),
+            u16::MAX
+        );
+        assert_eq!(LicenseDate::from_days(19_358).to_ymd(), (2023, 1, 1));
+
+        assert!(LicenseDate::from_ymd(2023, 2, 29).is_err());
+        assert!(LicenseDate::from_ymd(1969, 12, 31).is_err());
+        assert!(LicenseDate::from_ymd(2149, 6, 7).is_err());
+        assert!(LicenseDate::from_ymd(2023, 13, 1).is_err());
+        assert!(LicenseDate::from_ymd(99_999_999_999_999_999, 1, 1).is_err());
+        assert!(LicenseDate::from_ymd(i64::MIN, 1, 1).is_err());
+    }
+
+    #[test]
+    fn validate_license_date_format() {
+        let date: LicenseDate = "2024-02-29".parse().unwrap();
+
+        assert_eq!(date, LicenseDate::from_ymd(2024, 2, 29).unwrap());
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(LicenseDate::from_days(0).to_string(), "1970-01-01");
+        assert!(matches!(
+            "2023-02-29".parse::<LicenseDate>(),
+            Err(LicenseError::InvalidDate { .. })
+        ));
+        for date in ["29.02.2024", "2024-02", "2024-02-29-01", "2024-02-xx"] {
+            assert_eq!(
+                date.parse::<LicenseDate>(),
+                Err(LicenseError::InvalidDateFormat {
+                    date: date.to_string()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn validate_license_expiration_packing() {
+        let expiration = LicenseExpiration::new(
+            LicenseDate::from_ymd(2023, 1, 1).unwrap(),
+            LicenseDate::from_ymd(2024, 1, 1).unwrap(),
+        )
+        .unwrap();
+
+        let bytes = expiration.to_bytes();
+        assert_eq!(bytes, [0x4B, 0x9E, 0x4D, 0x0B]);
+        assert_eq!(LicenseExpiration::from_bytes(&bytes).unwrap(), expiration);
+
+        assert!(expiration.is_expired(LicenseDate::from_ymd(2022, 12, 31).unwrap()));
+        assert!(!expiration.is_expired(LicenseDate::from_ymd(2023, 1, 1).unwrap()));
+        assert!(!expiration.is_expired(LicenseDate::from_ymd(2024, 1, 1).unwrap()));
+        assert!(expiration.is_expired(LicenseDate::from_ymd(2024, 1, 2).unwrap()));
+
+        assert_eq!(
+            LicenseExpiration::new(expiration.expires, expiration.issued),
+            Err(LicenseError::InvalidExpiration {
+                issued: 19_723,
+                expires: 19_358
+            })
+        );
+    }
+}
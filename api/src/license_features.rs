@@ -0,0 +1,117 @@
+//! # License features
+//!
+//! Named schema of features which can be enabled by license key. Enabled features are stored in
+//! license key as bitfield, where bit of feature is given by its position in schema.
+
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
+use crate::magic::Result;
+
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct LicenseFeatures {
+    names: Vec<String>,
+}
+
+impl LicenseFeatures {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Order of feature names is part of license key layout, new features can be only appended
+    /// so previously issued license keys keep their features. Appending feature keeps previously
+    /// issued license keys valid only while [byte_size](LicenseFeatures::byte_size) stays same,
+    /// 9th, 17th, ... feature grows bitfield and shrinks seed hash, so every previously issued
+    /// license key becomes invalid.
+    pub fn new(names: Vec<String>) -> Result<Self> {
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(LicenseError::DuplicateFeature { name: name.clone() });
+            }
+        }
+
+        Ok(LicenseFeatures { names })
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    /// Size of bitfield in license key.
+    #[inline(always)]
+    pub fn byte_size(&self) -> usize {
+        self.names.len().div_ceil(8)
+    }
+
+    #[inline(always)]
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn encode(&self, enabled: &[String]) -> Result<Vec<u8>> {
+        let mut bitfield = vec![0; self.byte_size()];
+
+        for name in enabled {
+            match self.position(name) {
+                Some(position) => bitfield[position / 8] |= 1 << (position % 8),
+                None => return Err(LicenseError::UnknownFeature { name: name.clone() }),
+            }
+        }
+
+        Ok(bitfield)
+    }
+
+    /// Names of features enabled in bitfield, bits outside of schema are ignored.
+    pub fn decode(&self, bitfield: &[u8]) -> Vec<String> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter(|(position, _)| match bitfield.get(position / 8) {
+                Some(byte) => byte & (1 << (position % 8)) != 0,
+                None => false,
+            })
+            .map(|(_, name)| name.clone())
+            .collect()
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_names(&self) -> &Vec<String> {
+        &self.names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_features::LicenseFeatures;
+    use crate::magic::prelude::*;
+    use crate::LicenseError;
+
+    #[test]
+    fn validate_license_features_bitfield() {
+        let names: Vec<String> = (0..10).map(|i| format!("feature_{}", i)).collect();
+        let features = LicenseFeatures::new(names).unwrap();
+
+        let enabled = Vec::from(["feature_0".to_string(), "feature_9".to_string()]);
+        let bitfield = features.encode(&enabled).unwrap();
+
+        assert_eq!(features.byte_size(), 2);
+        assert_eq!(bitfield, Vec::from([0b0000_0001, 0b0000_0010]));
+        assert_eq!(features.decode(&bitfield), enabled);
+
+        assert_eq!(
+            features.encode(&["export_pdf".to_string()]),
+            Err(LicenseError::UnknownFeature {
+                name: "export_pdf".to_string()
+            })
+        );
+        assert_eq!(
+            LicenseFeatures::new(Vec::from(["sync".to_string(), "sync".to_string()])),
+            Err(LicenseError::DuplicateFeature {
+                name: "sync".to_string()
+            })
+        );
+    }
+}
@@ -1,34 +1,79 @@
+use crate::license_error::LicenseError;
+use crate::license_expiration::LicenseExpiration;
+#[cfg(feature = "serde")]
+use crate::license_serializer::{DefaultLicenseKeySerializer, LicenseKeySerializer};
+use crate::magic::prelude::*;
 use crate::magic::Result;
 
-use simple_error::bail;
-
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LicenseKeyStatus {
     Valid,
     Invalid,
     Blacklisted,
+    InvalidSignature,
+    Expired,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LicenseKeyProperties {
+    /// Size of generation header, zero when operator has no generation.
+    pub generation_size: usize,
     pub key_size: usize,
     pub payload_size: usize,
+    pub features_size: usize,
+    pub expiration_size: usize,
     pub checksum_size: usize,
+    pub signature_size: usize,
 }
 
 impl LicenseKeyProperties {
+    /// Size of license key, saturates at [usize::MAX] for properties of untrusted license keys,
+    /// which no license key can fit.
     pub fn size(&self) -> usize {
-        self.key_size + self.payload_size + self.checksum_size
+        [
+            self.generation_size,
+            self.key_size,
+            self.payload_size,
+            self.features_size,
+            self.expiration_size,
+            self.checksum_size,
+            self.signature_size,
+        ]
+        .iter()
+        .fold(0, |size, section| size.saturating_add(*section))
     }
 }
 
+/// Optional content of generated license key.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct LicenseKeyOptions {
+    pub expiration: Option<LicenseExpiration>,
+    /// Names of enabled features from operator feature schema.
+    pub features: Vec<String>,
+}
+
+/// License key split into sections of license key layout.
+///
+/// With `serde` feature, license key is serialized as its raw bytes encoded by built-in serializers
+/// (uppercase hexadecimal) together with its properties, sections are split again on
+/// deserialization. Enabled features are not serialized, as stored record could unlock them, use
+/// [serde_key](crate::license_operator::LicenseOperator::serde_key) to store license key typed by
+/// customer and decode its features by operator feature schema.
 #[derive(PartialEq, Eq, Clone, Debug, Default)]
 pub struct LicenseKey {
     pub properties: LicenseKeyProperties,
+    pub generation: Vec<u8>,
     pub seed: Vec<u8>,
     pub payload: Vec<u8>,
+    pub features: Vec<u8>,
+    pub expiration: Vec<u8>,
     pub checksum: Vec<u8>,
+    pub signature: Vec<u8>,
     pub serialized_key: Vec<u8>,
+    /// Names of features enabled by license key, resolved by operator feature schema.
+    pub enabled_features: Vec<String>,
 }
 
 impl LicenseKey {
@@ -46,10 +91,15 @@ impl LicenseKey {
     ) -> Self {
         LicenseKey {
             properties,
+            generation: Vec::new(),
             seed: key,
             payload,
+            features: Vec::new(),
+            expiration: Vec::new(),
             checksum,
+            signature: Vec::new(),
             serialized_key,
+            enabled_features: Vec::new(),
         }
     }
 
@@ -60,28 +110,129 @@ impl LicenseKey {
     #[inline(always)]
     pub fn deserialize(&self) -> Result<Self> {
         if self.serialized_key.len() < self.properties.size() {
-            bail!("Cannot deserialize license key with larger properties than raw key itself!");
+            return Err(LicenseError::KeyTooShort {
+                required: self.properties.size(),
+                actual: self.serialized_key.len(),
+            });
         }
 
+        // Split serialized key into sections in order of license key layout
+        let mut sections = self.serialized_key.as_slice();
+        let mut next_section = |size: usize| {
+            let (section, rest) = sections.split_at(size);
+            sections = rest;
+            section.to_vec()
+        };
+
         Ok(LicenseKey {
             properties: self.properties.clone(),
-            seed: self.serialized_key[0..self.properties.key_size].to_vec(),
-            payload: self.serialized_key
-                [self.properties.key_size..self.properties.key_size + self.properties.payload_size]
-                .to_vec(),
-            checksum: self.serialized_key[self.properties.key_size + self.properties.payload_size
-                ..self.properties.key_size
-                    + self.properties.payload_size
-                    + self.properties.checksum_size]
-                .to_vec(),
+            generation: next_section(self.properties.generation_size),
+            seed: next_section(self.properties.key_size),
+            payload: next_section(self.properties.payload_size),
+            features: next_section(self.properties.features_size),
+            expiration: next_section(self.properties.expiration_size),
+            checksum: next_section(self.properties.checksum_size),
+            signature: next_section(self.properties.signature_size),
             serialized_key: self.serialized_key.clone(),
+            enabled_features: self.enabled_features.clone(),
         })
     }
+
+    /// License key sections protected by checksum.
+    pub fn checksum_data(&self) -> Vec<u8> {
+        let mut data = self.generation.clone();
+        data.extend(&self.seed);
+        data.extend(&self.payload);
+        data.extend(&self.features);
+        data.extend(&self.expiration);
+        data
+    }
+
+    /// License key sections protected by signature.
+    pub fn signature_data(&self) -> Vec<u8> {
+        let mut data = self.checksum_data();
+        data.extend(&self.checksum);
+        data
+    }
+
+    /// Check whether feature is enabled by license key, license key should be validated first.
+    #[inline(always)]
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.enabled_features.iter().any(|feature| feature == name)
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    /// Generation of operator which issued license key, none for license key without generation.
+    #[inline(always)]
+    pub fn get_generation(&self) -> Option<u8> {
+        self.generation.first().copied()
+    }
+
+    /// Issue and expiry date of license key, none when license key does not expire.
+    pub fn get_expiration(&self) -> Option<LicenseExpiration> {
+        match self.expiration.is_empty() {
+            true => None,
+            false => LicenseExpiration::from_bytes(&self.expiration).ok(),
+        }
+    }
+}
+
+/// Serialized form of [LicenseKey].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedLicenseKey {
+    key: String,
+    properties: LicenseKeyProperties,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LicenseKey {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let key = DefaultLicenseKeySerializer {}.serialize_key(&self.serialized_key);
+
+        let mut state = serializer.serialize_struct("LicenseKey", 2)?;
+        state.serialize_field("key", &key)?;
+        state.serialize_field("properties", &self.properties)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for LicenseKey {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let serialized = SerializedLicenseKey::deserialize(deserializer)?;
+        let serialized_key = DefaultLicenseKeySerializer {}
+            .deserialize_key(serialized.key)
+            .map_err(D::Error::custom)?;
+
+        LicenseKey {
+            properties: serialized.properties,
+            serialized_key,
+            ..Default::default()
+        }
+        .deserialize()
+        .map_err(D::Error::custom)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "serde")]
+    use crate::license_key::LicenseKeyStatus;
     use crate::license_key::{LicenseKey, LicenseKeyProperties};
+    use crate::magic::prelude::*;
 
     #[test]
     fn license_key_validate_deserialization() {
@@ -89,9 +240,13 @@ mod tests {
         let payload: Vec<u8> = Vec::from([0x05, 0x06, 0x07, 0x08]);
         let checksum: Vec<u8> = Vec::from([0x09, 0x0A, 0x0B, 0x0C]);
         let properties: LicenseKeyProperties = LicenseKeyProperties {
+            generation_size: 0,
             key_size: 4,
             payload_size: 4,
+            features_size: 0,
+            expiration_size: 0,
             checksum_size: 4,
+            signature_size: 0,
         };
 
         let mut raw_key: Vec<u8> = Vec::new();
@@ -99,9 +254,14 @@ mod tests {
         raw_key.extend(payload.clone());
         raw_key.extend(checksum.clone());
         let manual_license_key = LicenseKey {
+            generation: Vec::new(),
             seed: key.clone(),
             payload: payload.clone(),
+            features: Vec::new(),
+            expiration: Vec::new(),
             checksum: checksum.clone(),
+            signature: Vec::new(),
+            enabled_features: Vec::new(),
             properties: properties.clone(),
             serialized_key: raw_key.clone(),
         };
@@ -119,8 +279,64 @@ mod tests {
                 assert_eq!(valid, manual_license_key);
             }
             Err(_) => {
-                assert!(false)
+                unreachable!()
             }
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn validate_license_key_serde() {
+        let properties = LicenseKeyProperties {
+            generation_size: 1,
+            key_size: 4,
+            payload_size: 2,
+            features_size: 1,
+            expiration_size: 0,
+            checksum_size: 2,
+            signature_size: 0,
+        };
+        let license_key = LicenseKey::new(
+            properties,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::from([0x01, 0xAB, 0xCD, 0xEF, 0x10, 0x20, 0x30, 0x01, 0xFE, 0xFF]),
+        )
+        .deserialize()
+        .unwrap();
+
+        // License key is encoded as text instead of byte arrays
+        let json = serde_json::to_value(&license_key).unwrap();
+        assert_eq!(json["key"], "01ABCDEF10203001FEFF");
+        assert_eq!(json["properties"]["checksum_size"], 2);
+        assert!(json.get("enabled_features").is_none());
+
+        let deserialized: LicenseKey = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, license_key);
+
+        // Forged features are not trusted
+        let mut forged_json = serde_json::to_value(&license_key).unwrap();
+        forged_json["enabled_features"] = serde_json::json!(["export_pdf"]);
+        let forged: LicenseKey = serde_json::from_value(forged_json).unwrap();
+        assert!(!forged.has_feature("export_pdf"));
+        assert_eq!(deserialized.get_generation(), Some(0x01));
+        assert_eq!(deserialized.checksum, Vec::from([0xFE, 0xFF]));
+
+        // Key shorter than its properties is refused
+        let mut short_json = serde_json::to_value(&license_key).unwrap();
+        short_json["key"] = "01ABCDEF".into();
+        assert!(serde_json::from_value::<LicenseKey>(short_json).is_err());
+
+        // Overflowing properties are refused
+        for section in ["key_size", "checksum_size", "signature_size"] {
+            let mut overflowing_json = serde_json::to_value(&license_key).unwrap();
+            overflowing_json["properties"]["payload_size"] = usize::MAX.into();
+            overflowing_json["properties"][section] = usize::MAX.into();
+            assert!(serde_json::from_value::<LicenseKey>(overflowing_json).is_err());
+        }
+
+        let status = serde_json::to_string(&LicenseKeyStatus::Blacklisted).unwrap();
+        assert_eq!(status, "\"Blacklisted\"");
+    }
 }
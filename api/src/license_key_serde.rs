@@ -0,0 +1,173 @@
+//! # License key serde
+//!
+//! Serde adapters storing [LicenseKey] as text encoded by serializer of [LicenseOperator], which
+//! is exactly the license key typed by customer. Stored license key is parsed again by
+//! [parse_key](LicenseOperator::parse_key), so enabled features are always decoded from features
+//! bitfield by feature schema of operator.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! # use offline_license_rs::license_operator::LicenseOperator;
+//! use serde::de::DeserializeSeed;
+//!
+//! let operator = LicenseOperator::default(2, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+//! let license_key = operator.generate_license_key(b"sample.name@sample.domain.com").unwrap();
+//!
+//! let json = serde_json::to_string(&operator.serde_key(&license_key)).unwrap();
+//! let mut deserializer = serde_json::Deserializer::from_str(&json);
+//! let stored_key = operator.serde_key_seed().deserialize(&mut deserializer).unwrap();
+//! assert_eq!(stored_key, license_key);
+//! # }
+//! ```
+
+use core::fmt::Formatter;
+
+use serde::de::{DeserializeSeed, Error, Visitor};
+use serde::{Deserializer, Serialize, Serializer};
+
+use crate::license_operator::LicenseOperator;
+use crate::LicenseKey;
+
+/// License key serialized as text encoded by serializer of operator, created by
+/// [serde_key](LicenseOperator::serde_key).
+pub struct OperatorLicenseKey<'a> {
+    operator: &'a LicenseOperator,
+    key: &'a LicenseKey,
+}
+
+impl<'a> OperatorLicenseKey<'a> {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    #[inline(always)]
+    pub fn new(operator: &'a LicenseOperator, key: &'a LicenseKey) -> Self {
+        OperatorLicenseKey { operator, key }
+    }
+}
+
+impl Serialize for OperatorLicenseKey<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.operator.get_serialized_key(self.key))
+    }
+}
+
+/// Deserializer of license key text parsed by operator, created by
+/// [serde_key_seed](LicenseOperator::serde_key_seed).
+#[derive(Clone, Copy)]
+pub struct OperatorLicenseKeySeed<'a> {
+    operator: &'a LicenseOperator,
+}
+
+impl<'a> OperatorLicenseKeySeed<'a> {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    #[inline(always)]
+    pub fn new(operator: &'a LicenseOperator) -> Self {
+        OperatorLicenseKeySeed { operator }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for OperatorLicenseKeySeed<'_> {
+    type Value = LicenseKey;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<LicenseKey, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for OperatorLicenseKeySeed<'_> {
+    type Value = LicenseKey;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("license key encoded by serializer of license operator")
+    }
+
+    fn visit_str<E: Error>(self, key: &str) -> Result<LicenseKey, E> {
+        self.operator.parse_key(key).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_blacklist::LicenseBlacklist;
+    use crate::license_byte_check::LicenseByteCheck;
+    use crate::license_checksum::LicenseChecksum;
+    use crate::license_features::LicenseFeatures;
+    use crate::license_key::{LicenseKeyOptions, LicenseKeyStatus};
+    use crate::license_magic::LicenseMagic;
+    use crate::license_operator::LicenseOperator;
+    use crate::license_properties::LicenseProperties;
+    use crate::license_serializer::LicenseKeySerializer;
+    use crate::magic::prelude::*;
+    use crate::magic::Result;
+    use serde::de::DeserializeSeed;
+
+    /// Serializer encoding license key as lowercase hexadecimal split into groups of four.
+    struct GroupedLicenseKeySerializer {}
+
+    impl LicenseKeySerializer for GroupedLicenseKeySerializer {
+        fn hash(&self, seed: &[u8], magic: &[u8]) -> u8 {
+            seed.iter().chain(magic).fold(0, |hash, x| hash ^ x)
+        }
+
+        fn deserialize_key(&self, key: String) -> Result<Vec<u8>> {
+            Ok(hex::decode(key.replace('-', ""))?)
+        }
+
+        fn serialize_key(&self, key: &[u8]) -> String {
+            key.chunks(2).map(hex::encode).collect::<Vec<_>>().join("-")
+        }
+    }
+
+    #[test]
+    fn validate_operator_license_key_serde() {
+        let magic = LicenseMagic::new(Vec::from([
+            Vec::from([0x02, 0x05]),
+            Vec::from([0x04, 0x0B]),
+        ]));
+        let byte_check = LicenseByteCheck::new(Vec::from([0, 1]), &magic).unwrap();
+        let operator = LicenseOperator::new(
+            LicenseProperties {
+                key_size: 16,
+                magic_size: 2,
+                magic_count: 2,
+            },
+            magic,
+            Box::new(GroupedLicenseKeySerializer {}),
+            LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]),
+            LicenseBlacklist::default(),
+            byte_check,
+        )
+        .with_features(LicenseFeatures::new(Vec::from(["export_pdf".to_string()])).unwrap());
+
+        let options = LicenseKeyOptions {
+            features: Vec::from(["export_pdf".to_string()]),
+            ..Default::default()
+        };
+        let license_key = operator
+            .generate_license_key_with_options(b"sample.name@sample.domain.com", &options)
+            .unwrap();
+
+        // License key is stored as text typed by customer
+        let json = serde_json::to_value(operator.serde_key(&license_key)).unwrap();
+        assert_eq!(json, operator.get_serialized_key(&license_key));
+        assert!(json.as_str().unwrap().contains('-'));
+
+        let stored_key = operator.serde_key_seed().deserialize(json).unwrap();
+        assert_eq!(stored_key, license_key);
+        assert!(stored_key.has_feature("export_pdf"));
+        assert_eq!(
+            operator.validate_license_key(&stored_key),
+            LicenseKeyStatus::Valid
+        );
+
+        assert!(operator
+            .serde_key_seed()
+            .deserialize(serde_json::Value::from("0102-0304"))
+            .is_err());
+    }
+}
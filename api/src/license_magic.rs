@@ -1,6 +1,12 @@
+use crate::magic::prelude::*;
+use core::fmt::{Debug, Formatter};
+#[cfg(feature = "std")]
 use rand::Rng;
+use zeroize::Zeroize;
 
-#[derive(PartialEq, Eq, Clone, Debug, Default)]
+/// Secret magic of license key payload, magic is wiped from memory on drop and redacted from
+/// [Debug] output.
+#[derive(PartialEq, Eq, Clone, Default)]
 pub struct LicenseMagic {
     magic: Vec<Vec<u8>>,
 }
@@ -24,11 +30,29 @@ impl LicenseMagic {
         self.magic.push(magic);
     }
 
+    /// License key payload contains one byte for each magic.
     #[inline(always)]
     pub fn payload_size(&self) -> usize {
-        self.magic.iter().map(|m| m.len()).sum()
+        self.magic.len()
     }
 
+    /// Copy of magic containing only magic at **positions**, other magic is left empty so
+    /// positions of payload bytes are preserved.
+    pub fn subset(&self, positions: &[usize]) -> LicenseMagic {
+        LicenseMagic {
+            magic: self
+                .magic
+                .iter()
+                .enumerate()
+                .map(|(position, m)| match positions.contains(&position) {
+                    true => m.clone(),
+                    false => Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[cfg(feature = "std")]
     #[inline(always)]
     pub fn randomize_magic(&mut self, magic_size: usize, magic_count: usize) {
         let mut rng = rand::thread_rng();
@@ -43,8 +67,43 @@ impl LicenseMagic {
     //                Getters & Setters
     // ==================================================
 
+    /// Raw secret magic, never log or display it.
     #[inline(always)]
-    pub fn get_magic(&self) -> &Vec<Vec<u8>> {
+    pub fn expose_secret(&self) -> &Vec<Vec<u8>> {
         &self.magic
     }
 }
+
+impl Debug for LicenseMagic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LicenseMagic")
+            .field("payload_size", &self.payload_size())
+            .field("magic", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl Drop for LicenseMagic {
+    fn drop(&mut self) {
+        self.magic.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_magic::LicenseMagic;
+    use crate::magic::prelude::*;
+
+    #[test]
+    fn validate_license_magic_redacted_debug() {
+        let magic = LicenseMagic::new(Vec::from([Vec::from([0xAB, 0xCD]), Vec::from([0xEF])]));
+        let debug = format!("{:?}", magic);
+
+        assert_eq!(
+            debug,
+            "LicenseMagic { payload_size: 2, magic: \"[REDACTED]\" }"
+        );
+        assert!(!debug.contains("171"));
+        assert_eq!(magic.expose_secret()[0], Vec::from([0xAB, 0xCD]));
+    }
+}
@@ -1,29 +1,53 @@
-use std::borrow::Borrow;
+use alloc::rc::Rc;
+use core::borrow::Borrow;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
 
-use crate::license_blacklist::LicenseBlacklist;
+use crate::license_blacklist::{LicenseBlacklist, LicenseBlacklistBackend, LicenseBlacklistEntry};
 use crate::license_byte_check::LicenseByteCheck;
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
 use crate::magic::Result;
 
 use sha3::{digest::ExtendableOutput, Shake256};
-use simple_error::bail;
 
 use crate::license_checksum::LicenseChecksum;
-use crate::license_key::LicenseKeyStatus;
+use crate::license_expiration::{LicenseClock, EXPIRATION_SIZE};
+use crate::license_features::LicenseFeatures;
+use crate::license_key::{LicenseKeyOptions, LicenseKeyProperties, LicenseKeyStatus};
+#[cfg(feature = "serde")]
+use crate::license_key_serde::{OperatorLicenseKey, OperatorLicenseKeySeed};
 use crate::license_magic::LicenseMagic;
+use crate::license_operator_builder::LicenseOperatorBuilder;
 use crate::license_properties::LicenseProperties;
-use crate::license_serializer::{DefaultLicenseKeySerializer, LicenseKeySerializer};
+#[cfg(feature = "std")]
+use crate::license_serializer::DefaultLicenseKeySerializer;
+use crate::license_serializer::LicenseKeySerializer;
+use crate::license_signature::{LicenseSignature, SIGNATURE_SIZE};
+use crate::license_validation::{LicenseValidationReport, LicenseValidationStage};
+use crate::license_verifier::LicenseVerifier;
 use crate::LicenseKey;
 
+/// Minimal size of seed hash section in license key.
+const MIN_SEED_HASH_SIZE: usize = 8;
+
 pub struct LicenseOperator {
     properties: LicenseProperties,
     magic: LicenseMagic,
 
-    serializer: Box<dyn LicenseKeySerializer>,
+    serializer: Rc<dyn LicenseKeySerializer>,
 
     checksum: LicenseChecksum,
 
-    blacklist: LicenseBlacklist,
+    blacklist: Box<dyn LicenseBlacklistBackend>,
+    #[cfg(feature = "std")]
+    blacklist_path: Option<PathBuf>,
     byte_check: LicenseByteCheck,
+
+    signature: Option<LicenseSignature>,
+    clock: Option<Rc<dyn LicenseClock>>,
+    features: LicenseFeatures,
+    generation: Option<u8>,
 }
 
 impl LicenseOperator {
@@ -31,6 +55,8 @@ impl LicenseOperator {
     //                   Constructor
     // ==================================================
 
+    /// License operator without cross-checks of its components, prefer
+    /// [builder](LicenseOperator::builder) which validates them.
     #[inline(always)]
     pub fn new(
         properties: LicenseProperties,
@@ -43,15 +69,50 @@ impl LicenseOperator {
         LicenseOperator {
             properties,
             magic,
-            serializer,
+            serializer: Rc::from(serializer),
             checksum,
-            blacklist,
+            blacklist: Box::new(blacklist),
+            #[cfg(feature = "std")]
+            blacklist_path: None,
             byte_check,
+            signature: None,
+            clock: None,
+            features: LicenseFeatures::default(),
+            generation: None,
         }
     }
 
+    /// Builder of license operator validating its components at
+    /// [build](LicenseOperatorBuilder::build).
+    #[inline(always)]
+    pub fn builder() -> LicenseOperatorBuilder {
+        LicenseOperatorBuilder::default()
+    }
+
+    /// License operator with blacklist loaded from **blacklist_path**, missing file is loaded as
+    /// empty blacklist. Seeds revoked by [revoke_seed](LicenseOperator::revoke_seed) are appended
+    /// to the file.
+    #[cfg(feature = "std")]
+    pub fn new_with_blacklist_file(
+        properties: LicenseProperties,
+        magic: LicenseMagic,
+        serializer: Box<dyn LicenseKeySerializer>,
+        checksum: LicenseChecksum,
+        blacklist_path: &Path,
+        byte_check: LicenseByteCheck,
+    ) -> Result<Self> {
+        let blacklist = LicenseBlacklist::load(blacklist_path)?;
+        let mut operator = Self::new(
+            properties, magic, serializer, checksum, blacklist, byte_check,
+        );
+        operator.blacklist_path = Some(blacklist_path.to_path_buf());
+
+        Ok(operator)
+    }
+
     /// Default license operator is not recommended for use in Production. We recommend to define
     /// your own license operator with **new** method.
+    #[cfg(feature = "std")]
     #[inline(always)]
     pub fn default(magic_size: usize, magic_count: usize, checksum_magic: [u8; 8]) -> Self {
         let mut license = LicenseOperator {
@@ -61,10 +122,16 @@ impl LicenseOperator {
                 magic_size,
             },
             magic: LicenseMagic::default(),
-            serializer: Box::new(DefaultLicenseKeySerializer {}),
+            serializer: Rc::new(DefaultLicenseKeySerializer {}),
             checksum: LicenseChecksum::default(checksum_magic),
-            blacklist: LicenseBlacklist::default(),
+            blacklist: Box::new(LicenseBlacklist::default()),
+            #[cfg(feature = "std")]
+            blacklist_path: None,
             byte_check: LicenseByteCheck::default(),
+            signature: None,
+            clock: None,
+            features: LicenseFeatures::default(),
+            generation: None,
         };
 
         license.magic.randomize_magic(magic_size, magic_count);
@@ -72,111 +139,522 @@ impl LicenseOperator {
         license
     }
 
+    /// Sign license keys with [Ed25519](LicenseSignature) signature appended after checksum.
+    ///
+    /// Issuer operator requires [signer](LicenseSignature::Signer) for generating license keys,
+    /// operator shipped with application should contain only
+    /// [verifier](LicenseSignature::Verifier).
+    #[inline(always)]
+    pub fn with_signature(mut self, signature: LicenseSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// Embed issue and expiry date into license keys, expiration is validated against **clock**.
+    ///
+    /// Every license key has to be generated with expiration by
+    /// [generate_license_key_with_options](LicenseOperator::generate_license_key_with_options).
+    #[inline(always)]
+    pub fn with_expiration(mut self, clock: Box<dyn LicenseClock>) -> Self {
+        self.clock = Some(Rc::from(clock));
+        self
+    }
+
+    /// Embed bitfield of enabled features defined by **features** schema into license keys.
+    #[inline(always)]
+    pub fn with_features(mut self, features: LicenseFeatures) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Prefix license keys with **generation** header, so license keys issued by previous
+    /// operator configuration can be dispatched to matching operator by
+    /// [LicenseOperatorRegistry](crate::license_operator_registry::LicenseOperatorRegistry).
+    #[inline(always)]
+    pub fn with_generation(mut self, generation: u8) -> Self {
+        self.generation = Some(generation);
+        self
+    }
+
+    /// Replace blacklist with another backend, e.g. compact
+    /// [BloomLicenseBlacklist](crate::license_bloom_blacklist::BloomLicenseBlacklist) for very
+    /// large revocation sets.
+    #[inline(always)]
+    pub fn with_blacklist(mut self, blacklist: Box<dyn LicenseBlacklistBackend>) -> Self {
+        self.blacklist = blacklist;
+        self
+    }
+
+    /// Extend blacklist by entries of blacklist file at **blacklist_path**, missing file is loaded
+    /// as empty blacklist. Seeds revoked by [revoke_seed](LicenseOperator::revoke_seed) are
+    /// appended to the file.
+    #[cfg(feature = "std")]
+    pub fn with_blacklist_file(mut self, blacklist_path: &Path) -> Result<Self> {
+        let blacklist = LicenseBlacklist::load(blacklist_path)?;
+        for entry in blacklist.get_entries() {
+            self.blacklist.push_entry(entry.clone());
+        }
+        self.blacklist_path = Some(blacklist_path.to_path_buf());
+
+        Ok(self)
+    }
+
+    /// Derive verifier validating only payload bytes at positions of **byte_check**.
+    ///
+    /// Verifier contains only magic referenced by **byte_check** and public key of signature, so
+    /// application shipped with verifier does not contain secrets needed for generating license
+    /// keys. Successive releases of application should check different payload bytes, so key
+    /// generator built from one release fails on another.
+    ///
+    /// Checksum is copied with its magic, as verifier has to recompute it. Do not ship verifiers
+    /// of operators with keyed checksum, e.g. [keyed_sha3](LicenseChecksum::keyed_sha3), its key
+    /// is readable from verifier. Checksum does not protect license keys against forgery, only
+    /// [signature](LicenseOperator::with_signature) does.
+    pub fn derive_verifier(&self, byte_check: LicenseByteCheck) -> LicenseVerifier {
+        LicenseVerifier::new(LicenseOperator {
+            properties: self.properties,
+            magic: self.magic.subset(byte_check.get_byte_positions()),
+            serializer: self.serializer.clone(),
+            checksum: self.checksum.clone(),
+            blacklist: self.blacklist.box_clone(),
+            #[cfg(feature = "std")]
+            blacklist_path: None,
+            byte_check,
+            signature: self.signature.as_ref().map(LicenseSignature::to_verifier),
+            clock: self.clock.clone(),
+            features: self.features.clone(),
+            generation: self.generation,
+        })
+    }
+
     // ==================================================
     //                    Operators
     // ==================================================
 
     #[inline(always)]
     pub fn add_seed_to_blacklist(&mut self, seed: &[u8]) {
-        self.blacklist.push(seed.to_vec());
+        self.blacklist
+            .push_entry(LicenseBlacklistEntry::new(seed.to_vec(), ""));
+    }
+
+    /// Add seed to blacklist with revocation **reason**, entry is appended to blacklist file when
+    /// operator was created by
+    /// [new_with_blacklist_file](LicenseOperator::new_with_blacklist_file).
+    pub fn revoke_seed(&mut self, seed: &[u8], reason: &str) -> Result<()> {
+        let entry = LicenseBlacklistEntry::new(seed.to_vec(), reason);
+
+        #[cfg(feature = "std")]
+        if let Some(path) = &self.blacklist_path {
+            LicenseBlacklist::append_to_file(path, &entry)?;
+        }
+        self.blacklist.push_entry(entry);
+
+        Ok(())
     }
 
     #[inline(always)]
     pub fn generate_license_key(&self, seed: &[u8]) -> Result<LicenseKey> {
+        self.generate_license_key_with_options(seed, &LicenseKeyOptions::default())
+    }
+
+    pub fn generate_license_key_with_options(
+        &self,
+        seed: &[u8],
+        options: &LicenseKeyOptions,
+    ) -> Result<LicenseKey> {
         // Validate user parameters
-        // Minimal 8 seed size, USER_PAYLOAD payload size and 4 checksum size
-        let license_key_required_size: usize = 8 + self.magic.payload_size() + 4;
-        if self.properties.key_size <= license_key_required_size {
-            bail!(
-                "Cannot generate license key with less than {} key size! [key_size={}]",
-                license_key_required_size,
-                self.properties.key_size
-            );
-        }
+        self.validate_key_size()?;
+        let properties = self.get_key_properties();
+
+        let features = self.features.encode(&options.features)?;
+        let expiration = match (&self.clock, options.expiration) {
+            (Some(_), Some(expiration)) => expiration.to_bytes().to_vec(),
+            (Some(_), None) => return Err(LicenseError::MissingExpiration),
+            (None, Some(_)) => return Err(LicenseError::ExpirationNotSupported),
+            (None, None) => Vec::new(),
+        };
 
-        let license_key_hash_size =
-            self.properties.key_size - self.checksum.get_byte_size() - self.magic.payload_size();
+        let mut license_key = LicenseKey {
+            properties,
+            ..Default::default()
+        };
 
-        let mut license_key = LicenseKey::default();
-        let mut serialized_license_key = Vec::<u8>::with_capacity(license_key_hash_size);
+        // Prepend generation
+        license_key.generation = self.generation.into_iter().collect();
+        let mut serialized_license_key = license_key.generation.clone();
 
-        // Hash seed
-        Shake256::digest_xof(seed, &mut serialized_license_key);
-        license_key.seed.extend(serialized_license_key.clone());
+        // Hash seed, XOF digest fills whole seed section
+        license_key.seed = vec![0; license_key.properties.key_size];
+        Shake256::digest_xof(seed, &mut license_key.seed);
+        serialized_license_key.extend_from_slice(&license_key.seed);
 
         // Generate payload
-        for m in self.magic.get_magic().iter() {
+        for m in self.magic.expose_secret().iter() {
             let payload = self.serializer.hash(license_key.seed.borrow(), m);
             serialized_license_key.push(payload);
             license_key.payload.push(payload);
         }
-        license_key.properties.payload_size = license_key.payload.len();
+
+        // Append features
+        serialized_license_key.extend_from_slice(&features);
+        license_key.enabled_features = self.features.decode(&features);
+        license_key.features = features;
+
+        // Append expiration
+        serialized_license_key.extend_from_slice(&expiration);
+        license_key.expiration = expiration;
 
         // Create checksum
         match self.checksum.generate(&serialized_license_key) {
             Ok(valid) => {
+                if valid.len() != license_key.properties.checksum_size {
+                    return Err(LicenseError::InvalidChecksumSize {
+                        expected: license_key.properties.checksum_size,
+                        actual: valid.len(),
+                    });
+                }
                 serialized_license_key.extend_from_slice(&valid);
                 license_key.checksum.extend_from_slice(&valid);
-                license_key.properties.checksum_size = valid.len();
             }
             Err(report) => return Err(report),
         }
 
+        // Sign license key
+        if let Some(signature) = &self.signature {
+            let valid = signature.sign(&serialized_license_key)?;
+            serialized_license_key.extend_from_slice(&valid);
+            license_key.signature = valid;
+        }
+
         license_key.serialized_key = serialized_license_key;
 
         Ok(license_key)
     }
 
+    /// Parse license key typed by user into [LicenseKey] ready for
+    /// [validate_license_key](LicenseOperator::validate_license_key).
+    ///
+    /// Key is decoded with operator serializer and split into sections by operator properties,
+    /// enabled features are resolved by operator feature schema.
+    pub fn parse_key(&self, key: &str) -> Result<LicenseKey> {
+        self.parse_serialized_key(self.serializer.deserialize_key(key.to_string())?)
+    }
+
+    /// Split bytes of license key decoded by serializer into sections of this operator.
+    pub(crate) fn parse_serialized_key(&self, serialized_key: Vec<u8>) -> Result<LicenseKey> {
+        let properties = self.get_key_properties();
+
+        if serialized_key.len() != properties.size() {
+            return Err(LicenseError::MalformedKey {
+                expected: properties.size(),
+                actual: serialized_key.len(),
+            });
+        }
+
+        let mut license_key = LicenseKey::new(
+            properties,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            serialized_key,
+        )
+        .deserialize()?;
+        license_key.enabled_features = self.features.decode(&license_key.features);
+
+        Ok(license_key)
+    }
+
     #[inline(always)]
     pub fn validate_license_key(&self, key: &LicenseKey) -> LicenseKeyStatus {
-        let license_key = key.deserialize();
-        match license_key {
-            Ok(valid) => {
-                // Validate checksum
-                if !self.checksum.validate(
-                    valid.seed.clone(),
-                    valid.payload.clone(),
-                    valid.checksum,
-                ) {
-                    return LicenseKeyStatus::Invalid;
-                }
+        self.validate_license_key_detailed(key, false).status()
+    }
 
-                // Validate seed from blacklist
-                if self.blacklist.is_blacklisted(valid.seed.clone()) {
-                    return LicenseKeyStatus::Blacklisted;
-                }
+    /// Validate license key and report result of every validation stage.
+    ///
+    /// Expected and actual values of stages are reported only with **expose_values**, they are
+    /// derived from operator secrets and should be used only for diagnostics.
+    pub fn validate_license_key_detailed(
+        &self,
+        key: &LicenseKey,
+        expose_values: bool,
+    ) -> LicenseValidationReport {
+        let mut report = LicenseValidationReport::new(expose_values);
 
-                // Validate payload with byte check
-                if !self.byte_check.validate(
-                    valid.payload.borrow(),
-                    self.serializer.borrow(),
-                    valid.seed.borrow(),
-                    self.magic.borrow(),
-                ) {
-                    return LicenseKeyStatus::Invalid;
-                }
+        // License key has to be split into sections of this operator
+        let properties = self.get_key_properties();
+        if key.properties != properties {
+            report.push(LicenseValidationStage::Deserialization, false, || {
+                (
+                    Some(Vec::from((properties.size() as u64).to_be_bytes())),
+                    Some(Vec::from((key.properties.size() as u64).to_be_bytes())),
+                )
+            });
+            return report;
+        }
+
+        let valid = match key.deserialize() {
+            Ok(valid) => {
+                report.push(LicenseValidationStage::Deserialization, true, || {
+                    (None, None)
+                });
+                valid
             }
-            Err(_) => return LicenseKeyStatus::Invalid,
+            Err(_) => {
+                report.push(LicenseValidationStage::Deserialization, false, || {
+                    (
+                        Some(Vec::from((key.properties.size() as u64).to_be_bytes())),
+                        Some(Vec::from((key.serialized_key.len() as u64).to_be_bytes())),
+                    )
+                });
+                return report;
+            }
+        };
+
+        // Validate generation
+        if let Some(generation) = self.generation {
+            let actual = valid.get_generation();
+            report.push(
+                LicenseValidationStage::Generation,
+                actual == Some(generation),
+                || {
+                    (
+                        Some(Vec::from([generation])),
+                        actual.map(|a| Vec::from([a])),
+                    )
+                },
+            );
+        }
+
+        // Validate checksum
+        let checksum_data = valid.checksum_data();
+        let checksum_passed = self.checksum.validate(&checksum_data, &valid.checksum);
+        report.push(LicenseValidationStage::Checksum, checksum_passed, || {
+            (
+                self.checksum.generate(&checksum_data).ok(),
+                Some(valid.checksum.clone()),
+            )
+        });
+
+        // Validate signature
+        if let Some(signature) = &self.signature {
+            let signature_passed = signature.verify(&valid.signature_data(), &valid.signature);
+            report.push(LicenseValidationStage::Signature, signature_passed, || {
+                (None, Some(valid.signature.clone()))
+            });
+        }
+
+        // Validate seed from blacklist
+        let blacklisted = self.blacklist.is_blacklisted(&valid.seed);
+        report.push(LicenseValidationStage::Blacklist, !blacklisted, || {
+            (None, Some(valid.seed.clone()))
+        });
+
+        // Validate payload with byte check
+        for &position in self.byte_check.get_byte_positions() {
+            let expected = LicenseByteCheck::expected_byte(
+                position,
+                self.serializer.borrow(),
+                valid.seed.borrow(),
+                self.magic.borrow(),
+            );
+            let actual = valid.payload.get(position).copied();
+
+            report.push(
+                LicenseValidationStage::ByteCheck(position),
+                LicenseByteCheck::byte_matches(expected, actual).into(),
+                || {
+                    (
+                        expected.map(|e| Vec::from([e])),
+                        actual.map(|a| Vec::from([a])),
+                    )
+                },
+            );
+        }
+
+        // Validate expiration
+        if let Some(clock) = &self.clock {
+            let today = clock.today();
+            let expiration = valid.get_expiration();
+            let expiration_passed = match expiration {
+                Some(expiration) => !expiration.is_expired(today),
+                None => false,
+            };
+
+            report.push(
+                LicenseValidationStage::Expiration,
+                expiration_passed,
+                || {
+                    (
+                        Some(today.get_days().to_be_bytes().to_vec()),
+                        expiration.map(|e| e.expires.get_days().to_be_bytes().to_vec()),
+                    )
+                },
+            );
         }
 
-        LicenseKeyStatus::Valid
+        report
     }
 
     // ==================================================
     //                Getters & Setters
     // ==================================================
 
+    /// Check that key size fits generation, minimal seed hash size, one payload byte per magic,
+    /// features, expiration and checksum.
+    pub(crate) fn validate_key_size(&self) -> Result<()> {
+        let properties = self.get_key_properties();
+        let license_key_required_size: usize = properties.generation_size
+            + MIN_SEED_HASH_SIZE
+            + properties.payload_size
+            + properties.features_size
+            + properties.expiration_size
+            + properties.checksum_size;
+        if self.properties.key_size < license_key_required_size {
+            return Err(LicenseError::InsufficientKeySize {
+                required: license_key_required_size,
+                key_size: self.properties.key_size,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// License key section sizes derived from operator configuration, generation header takes one
+    /// byte, payload contains one byte for each magic, features bitfield one bit per feature,
+    /// expiration packs two dates and seed hash fills rest of the key. Signature is appended over
+    /// key size.
+    pub fn get_key_properties(&self) -> LicenseKeyProperties {
+        let payload_size = self.magic.payload_size();
+        let features_size = self.features.byte_size();
+        let expiration_size = match self.clock {
+            Some(_) => EXPIRATION_SIZE,
+            None => 0,
+        };
+        let checksum_size = *self.checksum.get_byte_size();
+        let generation_size = match self.generation {
+            Some(_) => 1,
+            None => 0,
+        };
+
+        LicenseKeyProperties {
+            generation_size,
+            key_size: self.properties.key_size.saturating_sub(
+                generation_size + payload_size + features_size + expiration_size + checksum_size,
+            ),
+            payload_size,
+            features_size,
+            expiration_size,
+            checksum_size,
+            signature_size: match self.signature {
+                Some(_) => SIGNATURE_SIZE,
+                None => 0,
+            },
+        }
+    }
+
+    #[inline(always)]
+    pub fn get_generation(&self) -> Option<u8> {
+        self.generation
+    }
+
+    #[inline(always)]
+    pub fn get_properties(&self) -> &LicenseProperties {
+        &self.properties
+    }
+
+    #[inline(always)]
+    pub fn get_magic(&self) -> &LicenseMagic {
+        &self.magic
+    }
+
+    #[inline(always)]
+    pub fn get_serializer(&self) -> &dyn LicenseKeySerializer {
+        self.serializer.borrow()
+    }
+
+    #[inline(always)]
+    pub fn get_checksum(&self) -> &LicenseChecksum {
+        &self.checksum
+    }
+
+    #[inline(always)]
+    pub fn get_blacklist(&self) -> &dyn LicenseBlacklistBackend {
+        self.blacklist.as_ref()
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub(crate) fn get_blacklist_mut(&mut self) -> &mut dyn LicenseBlacklistBackend {
+        self.blacklist.as_mut()
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn get_blacklist_path(&self) -> Option<&Path> {
+        self.blacklist_path.as_deref()
+    }
+
+    #[inline(always)]
+    pub fn get_byte_check(&self) -> &LicenseByteCheck {
+        &self.byte_check
+    }
+
+    #[inline(always)]
+    pub fn get_signature(&self) -> Option<&LicenseSignature> {
+        self.signature.as_ref()
+    }
+
+    #[inline(always)]
+    pub fn get_features(&self) -> &LicenseFeatures {
+        &self.features
+    }
+
+    #[inline(always)]
+    pub fn has_expiration(&self) -> bool {
+        self.clock.is_some()
+    }
+
     #[inline(always)]
     pub fn get_serialized_key(&self, license_key: &LicenseKey) -> String {
         self.serializer.serialize_key(&license_key.serialized_key)
     }
+
+    /// Serde adapter storing license key as text encoded by serializer of this operator.
+    #[cfg(feature = "serde")]
+    #[inline(always)]
+    pub fn serde_key<'a>(&'a self, license_key: &'a LicenseKey) -> OperatorLicenseKey<'a> {
+        OperatorLicenseKey::new(self, license_key)
+    }
+
+    /// Serde adapter parsing stored license key by [parse_key](LicenseOperator::parse_key).
+    #[cfg(feature = "serde")]
+    #[inline(always)]
+    pub fn serde_key_seed(&self) -> OperatorLicenseKeySeed<'_> {
+        OperatorLicenseKeySeed::new(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::license_key::LicenseKeyStatus;
+    // Fixtures of tests requiring `std` are unused without it
+    #![cfg_attr(not(feature = "std"), allow(unused_imports))]
+
+    use crate::license_blacklist::LicenseBlacklist;
+    use crate::license_byte_check::LicenseByteCheck;
+    use crate::license_checksum::LicenseChecksum;
+    use crate::license_expiration::{FixedLicenseClock, LicenseDate, LicenseExpiration};
+    use crate::license_features::LicenseFeatures;
+    use crate::license_key::{LicenseKeyOptions, LicenseKeyProperties, LicenseKeyStatus};
+    use crate::license_magic::LicenseMagic;
     use crate::license_operator::LicenseOperator;
+    use crate::license_properties::LicenseProperties;
+    use crate::license_serializer::DefaultLicenseKeySerializer;
+    use crate::license_signature::LicenseSignature;
+    use crate::license_validation::LicenseValidationStage;
+    use crate::magic::prelude::*;
+    use crate::LicenseError;
 
+    #[cfg(feature = "std")]
     #[test]
     fn validate_license_key_validation() {
         let user_email = "sample.name@sample.domain.com";
@@ -192,4 +670,465 @@ mod tests {
             LicenseKeyStatus::Valid
         )
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_parsing() {
+        let license_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        // Hexadecimal key 000102..0F typed with dashes and trailing new line
+        let typed_key = "00010203-04050607-08090A0B-0C0D0E0F\n";
+
+        let license_key = license_op.parse_key(typed_key);
+
+        assert!(license_key.is_ok());
+        let license_key = license_key.unwrap();
+        assert_eq!(
+            license_key.properties,
+            LicenseKeyProperties {
+                generation_size: 0,
+                key_size: 11,
+                payload_size: 1,
+                features_size: 0,
+                expiration_size: 0,
+                checksum_size: 4,
+                signature_size: 0,
+            }
+        );
+        assert_eq!(license_key.seed, (0..11).collect::<Vec<u8>>());
+        assert_eq!(license_key.payload, Vec::from([11]));
+        assert_eq!(license_key.checksum, Vec::from([12, 13, 14, 15]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_parsing_failure() {
+        let license_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        // Not hexadecimal
+        assert!(matches!(
+            license_op.parse_key("NOT-A-LICENSE-KEY"),
+            Err(LicenseError::InvalidKeyEncoding { .. })
+        ));
+        // Truncated key
+        assert_eq!(
+            license_op.parse_key("0001-0203-0405"),
+            Err(LicenseError::MalformedKey {
+                expected: 16,
+                actual: 6
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_seed_hash() {
+        let license_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let first_key = license_op
+            .generate_license_key("first.name@sample.domain.com".as_bytes())
+            .unwrap();
+        let second_key = license_op
+            .generate_license_key("second.name@sample.domain.com".as_bytes())
+            .unwrap();
+
+        assert_eq!(first_key.properties.key_size, 11);
+        assert_eq!(first_key.seed.len(), first_key.properties.key_size);
+        assert_eq!(first_key.serialized_key.len(), 16);
+        assert_ne!(first_key.seed, Vec::from([0; 11]));
+        assert_ne!(first_key.seed, second_key.seed);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_serialization_round_trip() {
+        let user_email = "sample.name@sample.domain.com";
+
+        let license_op = LicenseOperator::default(2, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let license_key = license_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        let parsed_key = license_op
+            .parse_key(&license_op.get_serialized_key(&license_key))
+            .unwrap();
+
+        assert_eq!(parsed_key, license_key);
+        assert_eq!(
+            license_op.validate_license_key(&parsed_key),
+            LicenseKeyStatus::Valid
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_insufficient_key_size() {
+        // 16 key size cannot hold 8 seed hash bytes, 5 payload bytes and 4 checksum bytes
+        let license_op = LicenseOperator::default(5, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(
+            license_op.generate_license_key("sample.name@sample.domain.com".as_bytes()),
+            Err(LicenseError::InsufficientKeySize {
+                required: 17,
+                key_size: 16
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_signed_license_key_validation() {
+        let user_email = "sample.name@sample.domain.com";
+
+        let issuer_signature = LicenseSignature::signer(&[7; 32]);
+        let public_key = issuer_signature.get_public_key();
+
+        let issuer_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+            .with_signature(issuer_signature);
+        let license_key = issuer_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        let serialized_key = issuer_op.get_serialized_key(&license_key);
+
+        assert_eq!(license_key.properties.signature_size, 64);
+        assert_eq!(
+            issuer_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Valid
+        );
+
+        // Application operator knows only public key
+        let verifier_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+            .with_signature(LicenseSignature::verifier(&public_key).unwrap());
+        let parsed_key = verifier_op.parse_key(&serialized_key).unwrap();
+
+        assert_eq!(
+            verifier_op.validate_license_key(&parsed_key),
+            LicenseKeyStatus::Valid
+        );
+        assert_eq!(
+            verifier_op.generate_license_key(user_email.as_bytes()),
+            Err(LicenseError::MissingSigningKey)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_signed_license_key_forgery() {
+        let user_email = "sample.name@sample.domain.com";
+
+        let issuer_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+            .with_signature(LicenseSignature::signer(&[7; 32]));
+        let forger_op = LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+            .with_signature(LicenseSignature::signer(&[8; 32]));
+
+        // Key signed with different private key
+        let forged_key = forger_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        assert_eq!(
+            issuer_op.validate_license_key(&forged_key),
+            LicenseKeyStatus::InvalidSignature
+        );
+
+        // Key with tampered signature
+        let mut tampered_key = issuer_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        let last = tampered_key.serialized_key.len() - 1;
+        tampered_key.serialized_key[last] ^= 0xFF;
+        assert_eq!(
+            issuer_op.validate_license_key(&tampered_key),
+            LicenseKeyStatus::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn validate_license_key_detailed_report() {
+        let user_email = "sample.name@sample.domain.com";
+
+        let magic = LicenseMagic::new(Vec::from([
+            Vec::from([0x02, 0x05]),
+            Vec::from([0x04, 0x09]),
+            Vec::from([0x06, 0x0B]),
+        ]));
+        let byte_check = LicenseByteCheck::new(Vec::from([0, 2]), &magic).unwrap();
+        let mut license_op = LicenseOperator::new(
+            LicenseProperties {
+                key_size: 24,
+                magic_size: 3,
+                magic_count: 2,
+            },
+            magic,
+            Box::new(DefaultLicenseKeySerializer {}),
+            LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]),
+            LicenseBlacklist::default(),
+            byte_check,
+        );
+        let license_key = license_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+
+        let report = license_op.validate_license_key_detailed(&license_key, false);
+        assert_eq!(report.status(), LicenseKeyStatus::Valid);
+        assert_eq!(
+            report
+                .get_steps()
+                .iter()
+                .map(|step| step.stage.clone())
+                .collect::<Vec<_>>(),
+            Vec::from([
+                LicenseValidationStage::Deserialization,
+                LicenseValidationStage::Checksum,
+                LicenseValidationStage::Blacklist,
+                LicenseValidationStage::ByteCheck(0),
+                LicenseValidationStage::ByteCheck(2),
+            ])
+        );
+
+        // Forged payload with recomputed checksum passes checksum, but fails byte check
+        let mut forged_key = license_key.clone();
+        let payload_position = forged_key.properties.key_size + 2;
+        forged_key.serialized_key[payload_position] ^= 0xFF;
+        let checksum_position = payload_position + 1;
+        let checksum = license_op
+            .checksum
+            .generate(&forged_key.serialized_key[..checksum_position])
+            .unwrap();
+        forged_key.serialized_key[checksum_position..].copy_from_slice(&checksum);
+
+        let report = license_op.validate_license_key_detailed(&forged_key, false);
+        assert_eq!(report.status(), LicenseKeyStatus::Invalid);
+        let failed_steps: Vec<_> = report.failed_steps().collect();
+        assert_eq!(failed_steps.len(), 1);
+        assert_eq!(failed_steps[0].stage, LicenseValidationStage::ByteCheck(2));
+        assert_eq!(failed_steps[0].expected, None);
+        assert_eq!(failed_steps[0].actual, None);
+
+        let report = license_op.validate_license_key_detailed(&forged_key, true);
+        let failed_step = report.failed_steps().next().unwrap();
+        assert_eq!(
+            failed_step.expected,
+            Some(Vec::from([license_key.payload[2]]))
+        );
+        assert_eq!(
+            failed_step.actual,
+            Some(Vec::from([license_key.payload[2] ^ 0xFF]))
+        );
+
+        // Mistyped key fails checksum
+        let mut mistyped_key = license_key.clone();
+        mistyped_key.serialized_key[0] ^= 0x01;
+        let report = license_op.validate_license_key_detailed(&mistyped_key, true);
+        let failed_step = report.failed_steps().next().unwrap();
+        assert_eq!(failed_step.stage, LicenseValidationStage::Checksum);
+        assert_eq!(failed_step.expected.as_ref().unwrap().len(), 4);
+        assert_eq!(failed_step.actual, Some(license_key.checksum.clone()));
+
+        // Truncated key fails deserialization
+        let mut truncated_key = license_key.clone();
+        truncated_key.serialized_key.truncate(10);
+        let report = license_op.validate_license_key_detailed(&truncated_key, true);
+        assert_eq!(report.get_steps().len(), 1);
+        let failed_step = report.failed_steps().next().unwrap();
+        assert_eq!(failed_step.stage, LicenseValidationStage::Deserialization);
+        assert_eq!(failed_step.actual, Some(Vec::from(10_u64.to_be_bytes())));
+
+        // Key split by properties of another operator fails deserialization
+        let mut shifted_key = license_key.clone();
+        shifted_key.properties.key_size -= 1;
+        shifted_key.properties.payload_size += 1;
+        let report = license_op.validate_license_key_detailed(&shifted_key, true);
+        assert_eq!(report.status(), LicenseKeyStatus::Invalid);
+        assert_eq!(report.get_steps().len(), 1);
+        let failed_step = report.failed_steps().next().unwrap();
+        assert_eq!(failed_step.stage, LicenseValidationStage::Deserialization);
+
+        // Blacklisted key
+        license_op.add_seed_to_blacklist(&license_key.seed);
+        let report = license_op.validate_license_key_detailed(&license_key, false);
+        assert_eq!(report.status(), LicenseKeyStatus::Blacklisted);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_expiration() {
+        let user_email = "sample.name@sample.domain.com";
+        let expiration = LicenseExpiration::new(
+            LicenseDate::from_ymd(2023, 1, 1).unwrap(),
+            LicenseDate::from_ymd(2024, 1, 1).unwrap(),
+        )
+        .unwrap();
+        let options = LicenseKeyOptions {
+            expiration: Some(expiration),
+            ..Default::default()
+        };
+        let operator = |magic: &LicenseMagic, today: LicenseDate| {
+            LicenseOperator::new(
+                LicenseProperties {
+                    key_size: 20,
+                    magic_size: 1,
+                    magic_count: 3,
+                },
+                magic.clone(),
+                Box::new(DefaultLicenseKeySerializer {}),
+                LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]),
+                LicenseBlacklist::default(),
+                LicenseByteCheck::default(),
+            )
+            .with_expiration(Box::new(FixedLicenseClock { today }))
+        };
+        let magic = LicenseMagic::new(Vec::from([Vec::from([0x02, 0x05, 0x07])]));
+
+        let license_op = operator(&magic, LicenseDate::from_ymd(2023, 6, 1).unwrap());
+        let license_key = license_op
+            .generate_license_key_with_options(user_email.as_bytes(), &options)
+            .unwrap();
+        assert_eq!(license_key.get_expiration(), Some(expiration));
+        assert_eq!(license_key.serialized_key.len(), 20);
+        assert_eq!(
+            license_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Valid
+        );
+
+        // Parsed key keeps its expiration
+        let parsed_key = license_op
+            .parse_key(&license_op.get_serialized_key(&license_key))
+            .unwrap();
+        assert_eq!(parsed_key.get_expiration(), Some(expiration));
+
+        // Same key validated after its expiry date
+        let expired_op = operator(&magic, LicenseDate::from_ymd(2024, 1, 2).unwrap());
+        assert_eq!(
+            expired_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Expired
+        );
+
+        // Extended expiry date without new checksum is rejected
+        let mut extended_key = license_key.clone();
+        let expires_position = extended_key.properties.key_size + 1 + 2;
+        extended_key.serialized_key[expires_position] = 0xFF;
+        assert_eq!(
+            expired_op.validate_license_key(&extended_key),
+            LicenseKeyStatus::Invalid
+        );
+
+        // Expiration of generated key has to match operator
+        assert_eq!(
+            license_op.generate_license_key(user_email.as_bytes()),
+            Err(LicenseError::MissingExpiration)
+        );
+        assert_eq!(
+            LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+                .generate_license_key_with_options(user_email.as_bytes(), &options),
+            Err(LicenseError::ExpirationNotSupported)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_features() {
+        let user_email = "sample.name@sample.domain.com";
+        let features = LicenseFeatures::new(
+            ["export_pdf", "cloud_sync", "audit_log"]
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        )
+        .unwrap();
+
+        let license_op =
+            LicenseOperator::default(1, 3, [1, 2, 3, 4, 5, 6, 7, 8]).with_features(features);
+        let options = LicenseKeyOptions {
+            features: Vec::from(["audit_log".to_string(), "export_pdf".to_string()]),
+            ..Default::default()
+        };
+        let license_key = license_op
+            .generate_license_key_with_options(user_email.as_bytes(), &options)
+            .unwrap();
+
+        assert_eq!(license_key.features, Vec::from([0b0000_0101]));
+        assert_eq!(
+            license_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Valid
+        );
+
+        let parsed_key = license_op
+            .parse_key(&license_op.get_serialized_key(&license_key))
+            .unwrap();
+        assert_eq!(
+            license_op.validate_license_key(&parsed_key),
+            LicenseKeyStatus::Valid
+        );
+        assert!(parsed_key.has_feature("export_pdf"));
+        assert!(!parsed_key.has_feature("cloud_sync"));
+        assert!(parsed_key.has_feature("audit_log"));
+
+        // Upgraded features without new checksum are rejected
+        let mut upgraded_key = license_key.clone();
+        let features_position = upgraded_key.properties.key_size + 1;
+        upgraded_key.serialized_key[features_position] = 0b0000_0111;
+        assert_eq!(
+            license_op.validate_license_key(&upgraded_key),
+            LicenseKeyStatus::Invalid
+        );
+
+        let options = LicenseKeyOptions {
+            features: Vec::from(["unlimited".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            license_op.generate_license_key_with_options(user_email.as_bytes(), &options),
+            Err(LicenseError::UnknownFeature { .. })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_key_blacklist_file() {
+        let user_email = "sample.name@sample.domain.com";
+        let path = std::env::temp_dir().join(format!(
+            "offline-license-operator-{}.blacklist",
+            std::process::id()
+        ));
+        let operator = |path: &std::path::Path| {
+            LicenseOperator::new_with_blacklist_file(
+                LicenseProperties {
+                    key_size: 16,
+                    magic_size: 1,
+                    magic_count: 3,
+                },
+                LicenseMagic::new(Vec::from([Vec::from([0x02, 0x05, 0x09])])),
+                Box::new(DefaultLicenseKeySerializer {}),
+                LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]),
+                path,
+                LicenseByteCheck::default(),
+            )
+            .unwrap()
+        };
+
+        let mut license_op = operator(&path);
+        let license_key = license_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        license_op
+            .revoke_seed(&license_key.seed, "Refunded")
+            .unwrap();
+        assert_eq!(
+            license_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Blacklisted
+        );
+
+        // Revocation is persisted for next run
+        let reloaded_op = operator(&path);
+        assert_eq!(
+            reloaded_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Blacklisted
+        );
+        assert_eq!(
+            reloaded_op.get_blacklist().entries().unwrap()[0].reason,
+            "Refunded"
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
 }
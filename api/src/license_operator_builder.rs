@@ -0,0 +1,374 @@
+use crate::license_blacklist::{LicenseBlacklist, LicenseBlacklistBackend};
+use crate::license_byte_check::LicenseByteCheck;
+use crate::license_checksum::LicenseChecksum;
+use crate::license_error::LicenseError;
+use crate::license_expiration::LicenseClock;
+use crate::license_features::LicenseFeatures;
+use crate::license_magic::LicenseMagic;
+use crate::license_operator::LicenseOperator;
+use crate::license_properties::LicenseProperties;
+use crate::license_serializer::LicenseKeySerializer;
+use crate::license_signature::LicenseSignature;
+use crate::magic::prelude::*;
+use crate::magic::Result;
+
+/// Builder of [LicenseOperator] checking that its components fit together.
+///
+/// Properties, magic, serializer and checksum are required, remaining components are optional.
+/// [build](LicenseOperatorBuilder::build) validates magic against
+/// [properties](LicenseProperties), checksum size against output of its algorithm, byte check
+/// positions against magic and size of license key against all sections.
+///
+/// Every magic has to be present, only builder of [verifier](LicenseOperatorBuilder::for_verifier)
+/// accepts empty magic at positions not checked by its byte check.
+#[derive(Default)]
+pub struct LicenseOperatorBuilder {
+    properties: Option<LicenseProperties>,
+    magic: Option<LicenseMagic>,
+    serializer: Option<Box<dyn LicenseKeySerializer>>,
+    checksum: Option<LicenseChecksum>,
+    blacklist: Option<Box<dyn LicenseBlacklistBackend>>,
+    byte_positions: Vec<usize>,
+    signature: Option<LicenseSignature>,
+    clock: Option<Box<dyn LicenseClock>>,
+    features: Option<LicenseFeatures>,
+    generation: Option<u8>,
+    verifier: bool,
+}
+
+impl LicenseOperatorBuilder {
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    /// Build license operator, fails with descriptive [LicenseError] when components are missing
+    /// or do not fit together.
+    pub fn build(self) -> Result<LicenseOperator> {
+        let properties = Self::required(self.properties, "properties")?;
+        let magic = Self::required(self.magic, "magic")?;
+        let serializer = Self::required(self.serializer, "serializer")?;
+        let checksum = Self::required(self.checksum, "checksum")?;
+
+        if magic.payload_size() != properties.magic_size {
+            return Err(LicenseError::MagicSizeMismatch {
+                expected: properties.magic_size,
+                actual: magic.payload_size(),
+            });
+        }
+        for (position, entry) in magic.expose_secret().iter().enumerate() {
+            // Magic left empty by [LicenseMagic::subset] is not checked by verifiers
+            let unchecked = self.verifier && !self.byte_positions.contains(&position);
+            if !(unchecked && entry.is_empty()) && entry.len() != properties.magic_count {
+                return Err(LicenseError::MagicLengthMismatch {
+                    position,
+                    expected: properties.magic_count,
+                    actual: entry.len(),
+                });
+            }
+        }
+
+        // Checksum algorithm has to return checksum of declared size
+        checksum.generate(&[])?;
+
+        let byte_check = LicenseByteCheck::new(self.byte_positions, &magic)?;
+
+        let mut operator = LicenseOperator::new(
+            properties,
+            magic,
+            serializer,
+            checksum,
+            LicenseBlacklist::default(),
+            byte_check,
+        );
+        if let Some(blacklist) = self.blacklist {
+            operator = operator.with_blacklist(blacklist);
+        }
+        if let Some(signature) = self.signature {
+            operator = operator.with_signature(signature);
+        }
+        if let Some(clock) = self.clock {
+            operator = operator.with_expiration(clock);
+        }
+        if let Some(features) = self.features {
+            operator = operator.with_features(features);
+        }
+        if let Some(generation) = self.generation {
+            operator = operator.with_generation(generation);
+        }
+
+        operator.validate_key_size()?;
+
+        Ok(operator)
+    }
+
+    #[inline(always)]
+    fn required<T>(component: Option<T>, name: &str) -> Result<T> {
+        component.ok_or_else(|| LicenseError::MissingOperatorComponent {
+            component: name.to_string(),
+        })
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn with_properties(mut self, properties: LicenseProperties) -> Self {
+        self.properties = Some(properties);
+        self
+    }
+
+    #[inline(always)]
+    pub fn with_magic(mut self, magic: LicenseMagic) -> Self {
+        self.magic = Some(magic);
+        self
+    }
+
+    #[inline(always)]
+    pub fn with_serializer(mut self, serializer: Box<dyn LicenseKeySerializer>) -> Self {
+        self.serializer = Some(serializer);
+        self
+    }
+
+    #[inline(always)]
+    pub fn with_checksum(mut self, checksum: LicenseChecksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Blacklist backend, empty [LicenseBlacklist] by default.
+    #[inline(always)]
+    pub fn with_blacklist(mut self, blacklist: Box<dyn LicenseBlacklistBackend>) -> Self {
+        self.blacklist = Some(blacklist);
+        self
+    }
+
+    /// Payload positions checked by [LicenseByteCheck], validated against magic at build.
+    #[inline(always)]
+    pub fn with_byte_check(mut self, byte_positions: Vec<usize>) -> Self {
+        self.byte_positions = byte_positions;
+        self
+    }
+
+    /// See [LicenseOperator::with_signature].
+    #[inline(always)]
+    pub fn with_signature(mut self, signature: LicenseSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// See [LicenseOperator::with_expiration].
+    #[inline(always)]
+    pub fn with_expiration(mut self, clock: Box<dyn LicenseClock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// See [LicenseOperator::with_features].
+    #[inline(always)]
+    pub fn with_features(mut self, features: LicenseFeatures) -> Self {
+        self.features = Some(features);
+        self
+    }
+
+    /// See [LicenseOperator::with_generation].
+    #[inline(always)]
+    pub fn with_generation(mut self, generation: u8) -> Self {
+        self.generation = Some(generation);
+        self
+    }
+
+    /// Build operator of [verifier](crate::license_verifier::LicenseVerifier), magic at positions
+    /// not checked by byte check may be left empty.
+    #[inline(always)]
+    pub fn for_verifier(mut self) -> Self {
+        self.verifier = true;
+        self
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::adler32::adler32_checksum;
+    use crate::license_checksum::LicenseChecksum;
+    use crate::license_key::LicenseKeyStatus;
+    use crate::license_magic::LicenseMagic;
+    use crate::license_operator::LicenseOperator;
+    use crate::license_operator_builder::LicenseOperatorBuilder;
+    use crate::license_properties::LicenseProperties;
+    use crate::license_serializer::DefaultLicenseKeySerializer;
+    use crate::LicenseError;
+
+    fn magic(magic_size: usize, magic_count: usize) -> LicenseMagic {
+        let mut magic = LicenseMagic::default();
+        magic.randomize_magic(magic_size, magic_count);
+        magic
+    }
+
+    fn builder(magic_size: usize, magic_count: usize) -> LicenseOperatorBuilder {
+        LicenseOperator::builder()
+            .with_properties(LicenseProperties {
+                key_size: 24,
+                magic_size,
+                magic_count,
+            })
+            .with_magic(magic(4, 3))
+            .with_serializer(Box::new(DefaultLicenseKeySerializer {}))
+            .with_checksum(LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]))
+    }
+
+    #[test]
+    fn validate_license_operator_builder() {
+        let user_email = "sample.name@sample.domain.com";
+        let license_op = builder(4, 3)
+            .with_byte_check(Vec::from([0, 3]))
+            .build()
+            .unwrap();
+
+        let license_key = license_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        assert_eq!(
+            license_op.validate_license_key(&license_key),
+            LicenseKeyStatus::Valid
+        );
+    }
+
+    #[test]
+    fn validate_license_operator_builder_missing_component() {
+        let result = LicenseOperator::builder()
+            .with_properties(LicenseProperties {
+                key_size: 24,
+                magic_size: 4,
+                magic_count: 3,
+            })
+            .build();
+
+        assert_eq!(
+            result.err(),
+            Some(LicenseError::MissingOperatorComponent {
+                component: "magic".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn validate_license_operator_builder_magic_mismatch() {
+        assert_eq!(
+            builder(3, 3).build().err(),
+            Some(LicenseError::MagicSizeMismatch {
+                expected: 3,
+                actual: 4
+            })
+        );
+
+        let mut short_magic = LicenseMagic::new(Vec::from([
+            Vec::from([0x01, 0x02, 0x03]),
+            Vec::from([0x04, 0x05]),
+        ]));
+        short_magic.push(Vec::from([0x06, 0x07, 0x08]));
+        assert_eq!(
+            builder(3, 3).with_magic(short_magic).build().err(),
+            Some(LicenseError::MagicLengthMismatch {
+                position: 1,
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn validate_license_operator_builder_verifier_magic() {
+        // Magic not checked by verifier is left empty
+        assert!(builder(4, 3)
+            .with_magic(magic(4, 3).subset(&[1]))
+            .with_byte_check(Vec::from([1]))
+            .for_verifier()
+            .build()
+            .is_ok());
+
+        // Byte check of verifier needs its magic
+        assert_eq!(
+            builder(4, 3)
+                .with_magic(magic(4, 3).subset(&[1]))
+                .with_byte_check(Vec::from([0, 1]))
+                .for_verifier()
+                .build()
+                .err(),
+            Some(LicenseError::MagicLengthMismatch {
+                position: 0,
+                expected: 3,
+                actual: 0
+            })
+        );
+
+        // Issuer needs every magic
+        assert_eq!(
+            builder(4, 3)
+                .with_magic(magic(4, 3).subset(&[1]))
+                .with_byte_check(Vec::from([1]))
+                .build()
+                .err(),
+            Some(LicenseError::MagicLengthMismatch {
+                position: 0,
+                expected: 3,
+                actual: 0
+            })
+        );
+        assert_eq!(
+            builder(4, 3)
+                .with_magic(magic(4, 3).subset(&[]))
+                .build()
+                .err(),
+            Some(LicenseError::MagicLengthMismatch {
+                position: 0,
+                expected: 3,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn validate_license_operator_builder_checksum_mismatch() {
+        let checksum =
+            LicenseChecksum::new(Vec::from([1, 2, 3, 4, 5, 6, 7, 8]), 2, adler32_checksum);
+
+        assert_eq!(
+            builder(4, 3).with_checksum(checksum).build().err(),
+            Some(LicenseError::InvalidChecksumSize {
+                expected: 2,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn validate_license_operator_builder_byte_check_out_of_range() {
+        assert_eq!(
+            builder(4, 3)
+                .with_byte_check(Vec::from([1, 4]))
+                .build()
+                .err(),
+            Some(LicenseError::ByteCheckOutOfRange {
+                position: 4,
+                magic_count: 4
+            })
+        );
+    }
+
+    #[test]
+    fn validate_license_operator_builder_insufficient_key_size() {
+        let result = builder(4, 3)
+            .with_properties(LicenseProperties {
+                key_size: 12,
+                magic_size: 4,
+                magic_count: 3,
+            })
+            .build();
+
+        assert!(matches!(
+            result.err(),
+            Some(LicenseError::InsufficientKeySize { key_size: 12, .. })
+        ));
+    }
+}
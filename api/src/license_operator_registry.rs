@@ -0,0 +1,305 @@
+//! # License operator registry
+//!
+//! Registry of license operators by [generation](LicenseOperator::with_generation) of license key.
+//! Rotation of magic, serializer or checksum registers operator of new generation, while license
+//! keys issued by previous generations are still validated by their own operator.
+//!
+//! License keys issued before generation header was introduced are validated by
+//! [legacy operator](LicenseOperatorRegistry::set_legacy_operator) without generation. Legacy
+//! operator also receives license keys of unknown generation and license keys which are invalid
+//! for operator of their generation, as first byte of legacy license key can match generation by
+//! chance.
+
+use alloc::collections::BTreeMap;
+
+use crate::license_error::LicenseError;
+use crate::license_key::LicenseKeyStatus;
+use crate::license_operator::LicenseOperator;
+use crate::license_validation::LicenseValidationReport;
+use crate::magic::Result;
+use crate::LicenseKey;
+
+#[derive(Default)]
+pub struct LicenseOperatorRegistry {
+    operators: BTreeMap<u8, LicenseOperator>,
+    legacy: Option<LicenseOperator>,
+}
+
+impl LicenseOperatorRegistry {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Register operator under its generation, operator without generation is registered by
+    /// [set_legacy_operator](LicenseOperatorRegistry::set_legacy_operator).
+    pub fn register(&mut self, operator: LicenseOperator) -> Result<()> {
+        let generation = operator
+            .get_generation()
+            .ok_or(LicenseError::MissingGeneration)?;
+        if self.operators.contains_key(&generation) {
+            return Err(LicenseError::DuplicateGeneration { generation });
+        }
+
+        self.operators.insert(generation, operator);
+
+        Ok(())
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    /// Parse license key by operator of its generation, newest generation is tried first as
+    /// serializers of generations may differ. License key of unknown generation is parsed by
+    /// legacy operator.
+    pub fn parse_key(&self, key: &str) -> Result<LicenseKey> {
+        let mut error = None;
+
+        for (&generation, operator) in self.operators.iter().rev() {
+            match operator.parse_key(key) {
+                Ok(license_key) if license_key.get_generation() == Some(generation) => {
+                    return Ok(license_key)
+                }
+                Ok(license_key) => {
+                    error = Some(LicenseError::UnknownGeneration {
+                        generation: license_key.get_generation().unwrap_or_default(),
+                    })
+                }
+                Err(report) => {
+                    error.get_or_insert(report);
+                }
+            }
+        }
+
+        if let Some(legacy) = &self.legacy {
+            return legacy.parse_key(key);
+        }
+
+        Err(error.unwrap_or(LicenseError::MissingGeneration))
+    }
+
+    /// Validate license key by operator of its generation, license key which is invalid for
+    /// operator of its generation is validated by legacy operator. License key of unknown
+    /// generation without legacy operator is invalid.
+    pub fn validate_license_key(&self, key: &LicenseKey) -> LicenseKeyStatus {
+        let status = match self.get_operator_of(key) {
+            Some(operator) => operator.validate_license_key(key),
+            None => LicenseKeyStatus::Invalid,
+        };
+
+        match (status, self.get_legacy_key(key)) {
+            (LicenseKeyStatus::Invalid, Some((legacy, legacy_key))) => {
+                legacy.validate_license_key(&legacy_key)
+            }
+            (status, _) => status,
+        }
+    }
+
+    /// Validate license key by operator of its generation or legacy operator same as
+    /// [validate_license_key](LicenseOperatorRegistry::validate_license_key), none when no
+    /// operator can validate license key.
+    pub fn validate_license_key_detailed(
+        &self,
+        key: &LicenseKey,
+        expose_values: bool,
+    ) -> Option<LicenseValidationReport> {
+        let report = self
+            .get_operator_of(key)
+            .map(|operator| operator.validate_license_key_detailed(key, expose_values));
+
+        match (&report, self.get_legacy_key(key)) {
+            (None, Some((legacy, legacy_key))) => {
+                Some(legacy.validate_license_key_detailed(&legacy_key, expose_values))
+            }
+            (Some(generation_report), Some((legacy, legacy_key)))
+                if generation_report.status() == LicenseKeyStatus::Invalid =>
+            {
+                Some(legacy.validate_license_key_detailed(&legacy_key, expose_values))
+            }
+            _ => report,
+        }
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_operator(&self, generation: u8) -> Option<&LicenseOperator> {
+        self.operators.get(&generation)
+    }
+
+    /// Operator of license keys without generation header, issued before generations were
+    /// introduced.
+    #[inline(always)]
+    pub fn set_legacy_operator(&mut self, operator: LicenseOperator) {
+        self.legacy = Some(operator);
+    }
+
+    #[inline(always)]
+    pub fn get_legacy_operator(&self) -> Option<&LicenseOperator> {
+        self.legacy.as_ref()
+    }
+
+    /// Operator of newest generation, used for generating new license keys.
+    #[inline(always)]
+    pub fn get_latest_operator(&self) -> Option<&LicenseOperator> {
+        self.operators.values().next_back()
+    }
+
+    #[inline(always)]
+    fn get_operator_of(&self, key: &LicenseKey) -> Option<&LicenseOperator> {
+        match key.get_generation() {
+            Some(generation) => self.get_operator(generation),
+            None => self.legacy.as_ref(),
+        }
+    }
+
+    /// License key with generation header read again by legacy operator.
+    fn get_legacy_key(&self, key: &LicenseKey) -> Option<(&LicenseOperator, LicenseKey)> {
+        let legacy = self.legacy.as_ref()?;
+        key.get_generation()?;
+
+        legacy
+            .parse_serialized_key(key.serialized_key.clone())
+            .ok()
+            .map(|legacy_key| (legacy, legacy_key))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::license_key::LicenseKeyStatus;
+    use crate::license_operator::LicenseOperator;
+    use crate::license_operator_registry::LicenseOperatorRegistry;
+    use crate::LicenseError;
+
+    #[test]
+    fn validate_license_operator_registry_rotation() {
+        let user_email = "sample.name@sample.domain.com";
+        let first = LicenseOperator::default(2, 3, [1, 2, 3, 4, 5, 6, 7, 8]).with_generation(1);
+        let second = LicenseOperator::default(2, 3, [8, 7, 6, 5, 4, 3, 2, 1]).with_generation(2);
+
+        let old_key = first.generate_license_key(user_email.as_bytes()).unwrap();
+        let new_key = second.generate_license_key(user_email.as_bytes()).unwrap();
+        assert_eq!(old_key.get_generation(), Some(1));
+        assert_eq!(old_key.serialized_key[0], 1);
+
+        // Generation header is protected by checksum
+        let mut rewritten_key = old_key.clone();
+        rewritten_key.serialized_key[0] = 2;
+        assert_eq!(
+            second.validate_license_key(&rewritten_key),
+            LicenseKeyStatus::Invalid
+        );
+
+        let mut registry = LicenseOperatorRegistry::default();
+        registry.register(first).unwrap();
+        registry.register(second).unwrap();
+        assert_eq!(
+            registry.get_latest_operator().unwrap().get_generation(),
+            Some(2)
+        );
+
+        for license_key in [&old_key, &new_key] {
+            let serialized_key = registry
+                .get_operator(license_key.get_generation().unwrap())
+                .unwrap()
+                .get_serialized_key(license_key);
+            let parsed_key = registry.parse_key(&serialized_key).unwrap();
+            assert_eq!(parsed_key.generation, license_key.generation);
+            assert_eq!(
+                registry.validate_license_key(&parsed_key),
+                LicenseKeyStatus::Valid
+            );
+        }
+
+        assert_eq!(
+            registry.register(LicenseOperator::default(2, 3, [0; 8]).with_generation(2)),
+            Err(LicenseError::DuplicateGeneration { generation: 2 })
+        );
+        assert_eq!(
+            registry.register(LicenseOperator::default(2, 3, [0; 8])),
+            Err(LicenseError::MissingGeneration)
+        );
+
+        let unknown_key = LicenseOperator::default(2, 3, [0; 8])
+            .with_generation(3)
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        assert_eq!(
+            registry.validate_license_key(&unknown_key),
+            LicenseKeyStatus::Invalid
+        );
+    }
+
+    #[test]
+    fn validate_license_operator_registry_legacy() {
+        let legacy = LicenseOperator::default(2, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let current = LicenseOperator::default(2, 3, [8, 7, 6, 5, 4, 3, 2, 1]).with_generation(1);
+
+        // Legacy license keys without header, second one starts with byte of current generation
+        let legacy_keys: Vec<_> = (0..1024)
+            .map(|i| {
+                legacy
+                    .generate_license_key(format!("customer-{}@sample.domain.com", i).as_bytes())
+                    .unwrap()
+            })
+            .collect();
+        let legacy_key_with = |colliding: bool| {
+            let license_key = legacy_keys
+                .iter()
+                .find(|license_key| (license_key.serialized_key[0] == 1) == colliding)
+                .unwrap();
+            legacy.get_serialized_key(license_key)
+        };
+        let serialized_keys = [legacy_key_with(false), legacy_key_with(true)];
+        let current_key = current
+            .generate_license_key("sample.name@sample.domain.com".as_bytes())
+            .unwrap();
+        let serialized_current_key = current.get_serialized_key(&current_key);
+
+        let mut registry = LicenseOperatorRegistry::default();
+        registry.register(current).unwrap();
+        assert_eq!(
+            registry.parse_key(&serialized_keys[0]).err(),
+            Some(LicenseError::UnknownGeneration {
+                generation: legacy
+                    .parse_key(&serialized_keys[0])
+                    .unwrap()
+                    .serialized_key[0]
+            })
+        );
+        registry.set_legacy_operator(legacy);
+        // Colliding legacy license key is read as license key of current generation first
+        assert_eq!(
+            registry
+                .parse_key(&serialized_keys[1])
+                .unwrap()
+                .get_generation(),
+            Some(1)
+        );
+
+        for serialized_key in serialized_keys.iter() {
+            let parsed_key = registry.parse_key(serialized_key).unwrap();
+            assert_eq!(
+                registry.validate_license_key(&parsed_key),
+                LicenseKeyStatus::Valid
+            );
+            assert_eq!(
+                registry
+                    .validate_license_key_detailed(&parsed_key, false)
+                    .unwrap()
+                    .status(),
+                LicenseKeyStatus::Valid
+            );
+        }
+
+        let parsed_key = registry.parse_key(&serialized_current_key).unwrap();
+        assert_eq!(parsed_key.get_generation(), Some(1));
+        assert_eq!(
+            registry.validate_license_key(&parsed_key),
+            LicenseKeyStatus::Valid
+        );
+    }
+}
@@ -1,6 +1,9 @@
-#[derive(Copy, Clone)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LicenseProperties {
     pub key_size: usize,
+    /// Count of magic, one payload byte per magic.
     pub magic_size: usize,
+    /// Size of every magic in bytes.
     pub magic_count: usize,
 }
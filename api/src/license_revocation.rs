@@ -0,0 +1,189 @@
+//! # License revocation list
+//!
+//! Signed snapshot of [LicenseBlacklist] distributed to offline customers as a file, loaded by
+//! [LicenseVerifier::load_revocation_list](crate::license_verifier::LicenseVerifier::load_revocation_list).
+//!
+//! Revocation list is signed by issuer [signature](LicenseSignature) and carries monotonically
+//! increasing version, so customer cannot replace newer revocation list with older one. All numbers
+//! are big endian:
+//!
+//! | Field          | Size                          |
+//! |----------------|-------------------------------|
+//! | magic          | 4, [REVOCATION_LIST_MAGIC]    |
+//! | format version | 1                             |
+//! | version        | 8                             |
+//! | issued         | 2, days since Unix epoch      |
+//! | blacklist size | 4                             |
+//! | blacklist      | blacklist size, blacklist file |
+//! | signature      | 64, Ed25519 of fields above   |
+
+use crate::license_blacklist::LicenseBlacklist;
+use crate::license_error::LicenseError;
+use crate::license_expiration::LicenseDate;
+use crate::license_signature::{LicenseSignature, SIGNATURE_SIZE};
+use crate::magic::Result;
+
+/// Magic bytes identifying revocation list.
+pub const REVOCATION_LIST_MAGIC: [u8; 4] = *b"OLRL";
+/// Current version of revocation list format.
+pub const REVOCATION_LIST_FORMAT_VERSION: u8 = 1;
+
+const REVOCATION_LIST_HEADER_SIZE: usize = 19;
+
+#[derive(Clone)]
+pub struct LicenseRevocationList {
+    version: u64,
+    issued: LicenseDate,
+    blacklist: LicenseBlacklist,
+}
+
+impl LicenseRevocationList {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Every published revocation list should have higher **version** than previous one.
+    #[inline(always)]
+    pub fn new(version: u64, issued: LicenseDate, blacklist: LicenseBlacklist) -> Self {
+        LicenseRevocationList {
+            version,
+            issued,
+            blacklist,
+        }
+    }
+
+    /// Parse revocation list and verify its signature with issuer **signature**.
+    pub fn verify(bytes: &[u8], signature: &LicenseSignature) -> Result<Self> {
+        if bytes.len() < REVOCATION_LIST_HEADER_SIZE + SIGNATURE_SIZE {
+            return Err(invalid_revocation_list("Revocation list is too short"));
+        }
+
+        let (data, signed) = bytes.split_at(bytes.len() - SIGNATURE_SIZE);
+        if !signature.verify(data, signed) {
+            return Err(invalid_revocation_list("Invalid signature"));
+        }
+
+        if data[..4] != REVOCATION_LIST_MAGIC {
+            return Err(invalid_revocation_list("Missing revocation list magic"));
+        }
+        if data[4] != REVOCATION_LIST_FORMAT_VERSION {
+            return Err(invalid_revocation_list(&format!(
+                "Unsupported revocation list format version {}",
+                data[4]
+            )));
+        }
+
+        let version = u64::from_be_bytes(data[5..13].try_into().unwrap());
+        let issued = u16::from_be_bytes(data[13..15].try_into().unwrap());
+        let blacklist_size = u32::from_be_bytes(data[15..19].try_into().unwrap()) as usize;
+
+        let blacklist = &data[REVOCATION_LIST_HEADER_SIZE..];
+        if blacklist.len() != blacklist_size {
+            return Err(invalid_revocation_list(&format!(
+                "Blacklist size {} does not match {}",
+                blacklist.len(),
+                blacklist_size
+            )));
+        }
+
+        Ok(LicenseRevocationList {
+            version,
+            issued: LicenseDate::from_days(issued),
+            blacklist: LicenseBlacklist::from_bytes(blacklist)?,
+        })
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    /// Serialize revocation list signed by issuer [signer](LicenseSignature::Signer).
+    pub fn sign(&self, signature: &LicenseSignature) -> Result<Vec<u8>> {
+        let blacklist = self.blacklist.to_bytes()?;
+        let blacklist_size = u32::try_from(blacklist.len())
+            .map_err(|error| invalid_revocation_list(&error.to_string()))?;
+
+        let mut bytes = Vec::from(REVOCATION_LIST_MAGIC);
+        bytes.push(REVOCATION_LIST_FORMAT_VERSION);
+        bytes.extend(self.version.to_be_bytes());
+        bytes.extend(self.issued.get_days().to_be_bytes());
+        bytes.extend(blacklist_size.to_be_bytes());
+        bytes.extend(blacklist);
+
+        let signed = signature.sign(&bytes)?;
+        bytes.extend(signed);
+
+        Ok(bytes)
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_version(&self) -> u64 {
+        self.version
+    }
+
+    #[inline(always)]
+    pub fn get_issued(&self) -> LicenseDate {
+        self.issued
+    }
+
+    #[inline(always)]
+    pub fn get_blacklist(&self) -> &LicenseBlacklist {
+        &self.blacklist
+    }
+}
+
+#[inline(always)]
+fn invalid_revocation_list(reason: &str) -> LicenseError {
+    LicenseError::InvalidRevocationList {
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_blacklist::{LicenseBlacklist, LicenseBlacklistBackend};
+    use crate::license_expiration::LicenseDate;
+    use crate::license_revocation::LicenseRevocationList;
+    use crate::license_signature::LicenseSignature;
+    use crate::LicenseError;
+
+    #[test]
+    fn validate_revocation_list_signature() {
+        let issuer = LicenseSignature::generate();
+        let revocation_list = LicenseRevocationList::new(
+            3,
+            LicenseDate::from_ymd(2024, 6, 1).unwrap(),
+            LicenseBlacklist::new(Vec::from([Vec::from([0x01, 0x02, 0x03])])),
+        );
+
+        let bytes = revocation_list.sign(&issuer).unwrap();
+        let verified = LicenseRevocationList::verify(&bytes, &issuer.to_verifier()).unwrap();
+        assert_eq!(verified.get_version(), 3);
+        assert_eq!(verified.get_issued().to_ymd(), (2024, 6, 1));
+        assert!(verified.get_blacklist().is_blacklisted(&[0x01, 0x02, 0x03]));
+
+        // Verifier cannot sign revocation list
+        assert_eq!(
+            revocation_list.sign(&issuer.to_verifier()).err(),
+            Some(LicenseError::MissingSigningKey)
+        );
+
+        // Tampered revocation list
+        let mut tampered = bytes.clone();
+        tampered[12] ^= 0x01;
+        assert!(matches!(
+            LicenseRevocationList::verify(&tampered, &issuer.to_verifier()),
+            Err(LicenseError::InvalidRevocationList { .. })
+        ));
+
+        // Revocation list signed by another issuer
+        assert!(matches!(
+            LicenseRevocationList::verify(&bytes, &LicenseSignature::generate()),
+            Err(LicenseError::InvalidRevocationList { .. })
+        ));
+    }
+}
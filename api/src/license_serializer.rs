@@ -1,19 +1,28 @@
 #![allow(arithmetic_overflow)]
 
+use crate::magic::prelude::*;
 use crate::magic::Result;
 
-use std::num::Wrapping;
+use core::num::Wrapping;
+use hmac::{Mac, SimpleHmac};
+use sha3::Sha3_256;
 
 pub trait LicenseKeySerializer {
+    /// Name under which serializer is registered in
+    /// [LicenseRegistry](crate::license_config::LicenseRegistry), serializer is named **custom**
+    /// by default.
+    fn name(&self) -> &str {
+        "custom"
+    }
     fn hash(&self, seed: &[u8], magic: &[u8]) -> u8;
-    fn deserialize_key(&self, key: String) -> Vec<u8>;
+    fn deserialize_key(&self, key: String) -> Result<Vec<u8>>;
     fn serialize_key(&self, key: &[u8]) -> String;
 }
 
 pub type HashOperator = fn(&[u8], &[u8]) -> Result<Vec<u8>>;
 
-/// Default license serializer is not recommended for use in Production. We recommend to define
-/// your own license serializer.
+/// Default license serializer is not recommended for use in Production. We recommend
+/// [HmacLicenseKeySerializer] or your own license serializer.
 pub struct DefaultLicenseKeySerializer {}
 
 impl LicenseKeySerializer for DefaultLicenseKeySerializer {
@@ -21,6 +30,11 @@ impl LicenseKeySerializer for DefaultLicenseKeySerializer {
     //                    Operators
     // ==================================================
 
+    #[inline(always)]
+    fn name(&self) -> &str {
+        "default"
+    }
+
     #[inline(always)]
     fn hash(&self, seed: &[u8], magic: &[u8]) -> u8 {
         let mut hash: Wrapping<u8> = Wrapping(0);
@@ -40,9 +54,15 @@ impl LicenseKeySerializer for DefaultLicenseKeySerializer {
         hash.0
     }
 
+    /// Decode hexadecimal license key, dashes and whitespaces typed by user are ignored.
     #[inline(always)]
-    fn deserialize_key(&self, key: String) -> Vec<u8> {
-        key.into_bytes()
+    fn deserialize_key(&self, key: String) -> Result<Vec<u8>> {
+        let key: String = key
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .collect();
+
+        Ok(hex::decode(key)?)
     }
 
     #[inline(always)]
@@ -54,3 +74,72 @@ impl LicenseKeySerializer for DefaultLicenseKeySerializer {
         output
     }
 }
+
+/// License serializer deriving payload byte as first byte of HMAC-SHA3-256 of seed keyed by magic.
+///
+/// Payload byte depends on every bit and order of seed and magic, so payload bytes of one magic
+/// reveal nothing about payload bytes of another magic and byte checks cannot be forged without
+/// magic. License keys are serialized as uppercase hexadecimal like by
+/// [DefaultLicenseKeySerializer].
+///
+/// Payload bytes differ from [DefaultLicenseKeySerializer], so license keys issued with default
+/// serializer are invalid with this serializer. Keep operator with default serializer as previous
+/// [generation](crate::license_operator::LicenseOperator::with_generation) in
+/// [LicenseOperatorRegistry](crate::license_operator_registry::LicenseOperatorRegistry) and issue
+/// new license keys with operator of next generation using this serializer.
+pub struct HmacLicenseKeySerializer {}
+
+impl LicenseKeySerializer for HmacLicenseKeySerializer {
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    #[inline(always)]
+    fn name(&self) -> &str {
+        "hmac-sha3"
+    }
+
+    fn hash(&self, seed: &[u8], magic: &[u8]) -> u8 {
+        // HMAC accepts key of any size
+        let mut mac = SimpleHmac::<Sha3_256>::new_from_slice(magic).unwrap();
+        mac.update(seed);
+        mac.finalize().into_bytes()[0]
+    }
+
+    #[inline(always)]
+    fn deserialize_key(&self, key: String) -> Result<Vec<u8>> {
+        DefaultLicenseKeySerializer {}.deserialize_key(key)
+    }
+
+    #[inline(always)]
+    fn serialize_key(&self, key: &[u8]) -> String {
+        DefaultLicenseKeySerializer {}.serialize_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_serializer::{HmacLicenseKeySerializer, LicenseKeySerializer};
+    use crate::magic::prelude::*;
+
+    #[test]
+    fn validate_hmac_serializer_test_vectors() {
+        let serializer = HmacLicenseKeySerializer {};
+        let seed: Vec<u8> = (0..16).collect();
+
+        assert_eq!(serializer.hash(&seed, &[0x02, 0x05, 0x09]), 0xAE);
+        assert_eq!(serializer.hash(&seed, &[0x04, 0x0B, 0x0D]), 0x38);
+        assert_eq!(serializer.hash(&seed, &[]), 0xAC);
+        assert_eq!(
+            serializer.hash("sample.name@sample.domain.com".as_bytes(), &[1, 2, 3]),
+            0x63
+        );
+
+        // Payload byte depends on order of seed
+        let reversed: Vec<u8> = seed.iter().rev().copied().collect();
+        assert_ne!(
+            serializer.hash(&seed, &[0x02, 0x05, 0x09]),
+            serializer.hash(&reversed, &[0x02, 0x05, 0x09])
+        );
+    }
+}
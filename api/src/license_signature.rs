@@ -0,0 +1,121 @@
+//! # License signature
+//!
+//! Asymmetric [Ed25519](https://ed25519.cr.yp.to/) signature of license key. Issuer signs license
+//! keys with private key and shipped application verifies them only with public key, so secrets
+//! needed for generating license keys are never distributed with application.
+
+use crate::license_error::LicenseError;
+use crate::magic::prelude::*;
+use crate::magic::Result;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+/// Size of Ed25519 signature appended to signed license key.
+pub const SIGNATURE_SIZE: usize = ed25519_dalek::SIGNATURE_LENGTH;
+
+#[derive(Clone)]
+pub enum LicenseSignature {
+    /// Issuer side holding private key, able to sign and verify license keys.
+    Signer(SigningKey),
+    /// Application side holding only public key, able to verify license keys.
+    Verifier(VerifyingKey),
+}
+
+impl LicenseSignature {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    #[inline(always)]
+    pub fn signer(private_key: &[u8; 32]) -> Self {
+        LicenseSignature::Signer(SigningKey::from_bytes(private_key))
+    }
+
+    #[inline(always)]
+    pub fn verifier(public_key: &[u8; 32]) -> Result<Self> {
+        match VerifyingKey::from_bytes(public_key) {
+            Ok(verifying_key) => Ok(LicenseSignature::Verifier(verifying_key)),
+            Err(_) => Err(LicenseError::InvalidPublicKey),
+        }
+    }
+
+    /// Generate new random private key for license key issuer.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn generate() -> Self {
+        LicenseSignature::Signer(SigningKey::generate(&mut rand::rngs::OsRng))
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            LicenseSignature::Signer(signing_key) => {
+                Ok(signing_key.sign(message).to_bytes().to_vec())
+            }
+            LicenseSignature::Verifier(_) => Err(LicenseError::MissingSigningKey),
+        }
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        match Signature::from_slice(signature) {
+            Ok(signature) => self.get_verifying_key().verify(message, &signature).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Public part of signature, which can be shipped with application.
+    #[inline(always)]
+    pub fn to_verifier(&self) -> Self {
+        LicenseSignature::Verifier(self.get_verifying_key())
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_verifying_key(&self) -> VerifyingKey {
+        match self {
+            LicenseSignature::Signer(signing_key) => signing_key.verifying_key(),
+            LicenseSignature::Verifier(verifying_key) => *verifying_key,
+        }
+    }
+
+    #[inline(always)]
+    pub fn get_public_key(&self) -> [u8; 32] {
+        self.get_verifying_key().to_bytes()
+    }
+
+    /// Private key of issuer, none for verifier.
+    #[inline(always)]
+    pub fn get_private_key(&self) -> Option<[u8; 32]> {
+        match self {
+            LicenseSignature::Signer(signing_key) => Some(signing_key.to_bytes()),
+            LicenseSignature::Verifier(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::license_signature::{LicenseSignature, SIGNATURE_SIZE};
+
+    #[test]
+    fn validate_license_signature() {
+        let message = "sample.name@sample.domain.com".as_bytes();
+
+        let signer = LicenseSignature::signer(&[7; 32]);
+        let verifier = LicenseSignature::verifier(&signer.get_public_key()).unwrap();
+
+        let signature = signer.sign(message).unwrap();
+
+        assert_eq!(signature.len(), SIGNATURE_SIZE);
+        assert!(verifier.verify(message, &signature));
+        assert!(!verifier.verify("other.name@sample.domain.com".as_bytes(), &signature));
+        assert!(!verifier.verify(message, &signature[1..]));
+        assert!(verifier.sign(message).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! # License validation
+//!
+//! Detailed report of license key validation describing result of every validation stage, so it
+//! is possible to distinguish mistyped license key from forged or truncated one.
+
+use crate::license_key::LicenseKeyStatus;
+use crate::magic::prelude::*;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum LicenseValidationStage {
+    /// License key has properties of operator and size defined by them, sizes are reported as
+    /// big-endian `u64`.
+    Deserialization,
+    /// Generation header matches generation of operator.
+    Generation,
+    Checksum,
+    Signature,
+    Blacklist,
+    /// Byte check of payload at position.
+    ByteCheck(usize),
+    Expiration,
+}
+
+impl LicenseValidationStage {
+    /// Status of license key failing on this stage.
+    pub fn failure_status(&self) -> LicenseKeyStatus {
+        match self {
+            LicenseValidationStage::Signature => LicenseKeyStatus::InvalidSignature,
+            LicenseValidationStage::Blacklist => LicenseKeyStatus::Blacklisted,
+            LicenseValidationStage::Expiration => LicenseKeyStatus::Expired,
+            _ => LicenseKeyStatus::Invalid,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct LicenseValidationStep {
+    pub stage: LicenseValidationStage,
+    pub passed: bool,
+    /// Expected value, available only when values are exposed.
+    pub expected: Option<Vec<u8>>,
+    /// Actual value from license key, available only when values are exposed.
+    pub actual: Option<Vec<u8>>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct LicenseValidationReport {
+    expose_values: bool,
+    steps: Vec<LicenseValidationStep>,
+}
+
+impl LicenseValidationReport {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Expected and actual values are derived from secrets, expose them only for diagnostics and
+    /// never show them to user of license key.
+    #[inline(always)]
+    pub fn new(expose_values: bool) -> Self {
+        LicenseValidationReport {
+            expose_values,
+            steps: Vec::new(),
+        }
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    /// Record validation stage, values are evaluated only when they are exposed.
+    pub(crate) fn push<F>(&mut self, stage: LicenseValidationStage, passed: bool, values: F)
+    where
+        F: FnOnce() -> (Option<Vec<u8>>, Option<Vec<u8>>),
+    {
+        let (expected, actual) = match self.expose_values {
+            true => values(),
+            false => (None, None),
+        };
+
+        self.steps.push(LicenseValidationStep {
+            stage,
+            passed,
+            expected,
+            actual,
+        });
+    }
+
+    /// Status of license key given by first failed stage.
+    pub fn status(&self) -> LicenseKeyStatus {
+        match self.steps.iter().find(|step| !step.passed) {
+            Some(step) => step.stage.failure_status(),
+            None => LicenseKeyStatus::Valid,
+        }
+    }
+
+    pub fn failed_steps(&self) -> impl Iterator<Item = &LicenseValidationStep> {
+        self.steps.iter().filter(|step| !step.passed)
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_steps(&self) -> &Vec<LicenseValidationStep> {
+        &self.steps
+    }
+}
@@ -0,0 +1,293 @@
+//! # License verifier
+//!
+//! License operator restricted to validation of license keys, implementing
+//! [partial serial number verification](https://www.brandonstaggs.com/2007/07/26/implementing-a-partial-serial-number-verification-system-in-delphi/).
+//! Verifier is derived from full operator by
+//! [derive_verifier](crate::license_operator::LicenseOperator::derive_verifier) and embeds only
+//! magic checked by its byte check.
+//!
+//! Verifier of signed license keys also loads [signed revocation lists](LicenseRevocationList)
+//! distributed to offline customers.
+
+#[cfg(feature = "std")]
+use crate::license_error::LicenseError;
+use crate::license_expiration::LicenseClock;
+use crate::license_key::LicenseKeyStatus;
+use crate::license_operator::LicenseOperator;
+#[cfg(feature = "std")]
+use crate::license_revocation::LicenseRevocationList;
+use crate::license_validation::LicenseValidationReport;
+use crate::magic::prelude::*;
+use crate::magic::Result;
+use crate::LicenseKey;
+
+pub struct LicenseVerifier {
+    operator: LicenseOperator,
+    revocation_version: Option<u64>,
+}
+
+impl LicenseVerifier {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    #[inline(always)]
+    pub(crate) fn new(operator: LicenseOperator) -> Self {
+        LicenseVerifier {
+            operator,
+            revocation_version: None,
+        }
+    }
+
+    /// Restore **version** of last loaded revocation list, so older revocation list is refused
+    /// after restart of application.
+    #[inline(always)]
+    pub fn with_revocation_version(mut self, version: u64) -> Self {
+        self.revocation_version = Some(version);
+        self
+    }
+
+    /// Validate expiration of license keys against **clock**, e.g. on platform without system
+    /// time. Verifier has to be built from operator with expiration.
+    #[inline(always)]
+    pub fn with_expiration(mut self, clock: Box<dyn LicenseClock>) -> Self {
+        self.operator = self.operator.with_expiration(clock);
+        self
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    #[inline(always)]
+    pub fn parse_key(&self, key: &str) -> Result<LicenseKey> {
+        self.operator.parse_key(key)
+    }
+
+    #[inline(always)]
+    pub fn validate_license_key(&self, key: &LicenseKey) -> LicenseKeyStatus {
+        self.operator.validate_license_key(key)
+    }
+
+    #[inline(always)]
+    pub fn validate_license_key_detailed(
+        &self,
+        key: &LicenseKey,
+        expose_values: bool,
+    ) -> LicenseValidationReport {
+        self.operator
+            .validate_license_key_detailed(key, expose_values)
+    }
+
+    #[inline(always)]
+    pub fn add_seed_to_blacklist(&mut self, seed: &[u8]) {
+        self.operator.add_seed_to_blacklist(seed);
+    }
+
+    /// Verify revocation list with public key of operator signature and add its seeds to
+    /// blacklist.
+    ///
+    /// Revocation list with invalid signature or version older than last loaded revocation list
+    /// is refused and blacklist is left unchanged.
+    #[cfg(feature = "std")]
+    pub fn load_revocation_list(&mut self, bytes: &[u8]) -> Result<LicenseRevocationList> {
+        let signature =
+            self.operator
+                .get_signature()
+                .ok_or_else(|| LicenseError::InvalidRevocationList {
+                    reason: "Verifier without signature cannot verify revocation list".to_string(),
+                })?;
+        let revocation_list = LicenseRevocationList::verify(bytes, signature)?;
+
+        if let Some(current) = self.revocation_version {
+            if revocation_list.get_version() < current {
+                return Err(LicenseError::OutdatedRevocationList {
+                    version: revocation_list.get_version(),
+                    current,
+                });
+            }
+        }
+
+        let blacklist = self.operator.get_blacklist_mut();
+        for entry in revocation_list.get_blacklist().get_entries() {
+            if !blacklist.is_blacklisted(&entry.seed) {
+                blacklist.push_entry(entry.clone());
+            }
+        }
+        self.revocation_version = Some(revocation_list.get_version());
+
+        Ok(revocation_list)
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    #[inline(always)]
+    pub fn get_serialized_key(&self, license_key: &LicenseKey) -> String {
+        self.operator.get_serialized_key(license_key)
+    }
+
+    /// Version of last loaded revocation list.
+    #[inline(always)]
+    pub fn get_revocation_version(&self) -> Option<u64> {
+        self.revocation_version
+    }
+
+    /// Operator with partial magic, it cannot generate valid license keys.
+    #[inline(always)]
+    pub fn get_operator(&self) -> &LicenseOperator {
+        &self.operator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Fixtures of tests requiring `std` are unused without it
+    #![cfg_attr(not(feature = "std"), allow(unused_imports))]
+
+    use crate::license_blacklist::LicenseBlacklist;
+    use crate::license_byte_check::LicenseByteCheck;
+    use crate::license_checksum::LicenseChecksum;
+    use crate::license_expiration::LicenseDate;
+    use crate::license_key::LicenseKeyStatus;
+    use crate::license_magic::LicenseMagic;
+    use crate::license_operator::LicenseOperator;
+    use crate::license_properties::LicenseProperties;
+    #[cfg(feature = "std")]
+    use crate::license_revocation::LicenseRevocationList;
+    use crate::license_serializer::DefaultLicenseKeySerializer;
+    use crate::license_signature::LicenseSignature;
+    use crate::magic::prelude::*;
+    use crate::LicenseError;
+
+    fn issuer_operator() -> LicenseOperator {
+        let magic = LicenseMagic::new(Vec::from([
+            Vec::from([0x02, 0x05, 0x09]),
+            Vec::from([0x04, 0x0B, 0x0D]),
+            Vec::from([0x06, 0x11, 0x13]),
+            Vec::from([0x08, 0x15, 0x17]),
+        ]));
+
+        LicenseOperator::new(
+            LicenseProperties {
+                key_size: 24,
+                magic_size: 4,
+                magic_count: 3,
+            },
+            magic,
+            Box::new(DefaultLicenseKeySerializer {}),
+            LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]),
+            LicenseBlacklist::default(),
+            LicenseByteCheck::default(),
+        )
+    }
+
+    #[test]
+    fn validate_license_verifier_partial_magic() {
+        let user_email = "sample.name@sample.domain.com";
+        let issuer_op = issuer_operator();
+
+        let first_release = issuer_op.derive_verifier(
+            LicenseByteCheck::new(Vec::from([0, 1]), issuer_op.get_magic()).unwrap(),
+        );
+        let second_release = issuer_op.derive_verifier(
+            LicenseByteCheck::new(Vec::from([2, 3]), issuer_op.get_magic()).unwrap(),
+        );
+
+        // Verifier embeds only checked magic
+        let magic = first_release.get_operator().get_magic().expose_secret();
+        assert_eq!(magic.len(), 4);
+        assert_eq!(magic[0], issuer_op.get_magic().expose_secret()[0]);
+        assert_eq!(magic[1], issuer_op.get_magic().expose_secret()[1]);
+        assert!(magic[2].is_empty());
+        assert!(magic[3].is_empty());
+
+        // Genuine license key is valid in every release
+        let license_key = issuer_op
+            .generate_license_key(user_email.as_bytes())
+            .unwrap();
+        let serialized_key = issuer_op.get_serialized_key(&license_key);
+        for release in [&first_release, &second_release] {
+            let parsed_key = release.parse_key(&serialized_key).unwrap();
+            assert_eq!(
+                release.validate_license_key(&parsed_key),
+                LicenseKeyStatus::Valid
+            );
+        }
+
+        // Key generator built from first release fails on second release
+        let forged_key = first_release
+            .get_operator()
+            .generate_license_key("cracker@sample.domain.com".as_bytes())
+            .unwrap();
+        assert_eq!(
+            first_release.validate_license_key(&forged_key),
+            LicenseKeyStatus::Valid
+        );
+        assert_eq!(
+            second_release.validate_license_key(&forged_key),
+            LicenseKeyStatus::Invalid
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validate_license_verifier_revocation_list() {
+        let signature = LicenseSignature::generate();
+        let issuer_op = issuer_operator().with_signature(signature.clone());
+        let mut verifier = issuer_op.derive_verifier(LicenseByteCheck::default());
+
+        let license_key = issuer_op
+            .generate_license_key("sample.name@sample.domain.com".as_bytes())
+            .unwrap();
+        let revocation_list = |version: u64, seeds: Vec<Vec<u8>>| {
+            LicenseRevocationList::new(
+                version,
+                LicenseDate::from_ymd(2024, 6, 1).unwrap(),
+                LicenseBlacklist::new(seeds),
+            )
+            .sign(&signature)
+            .unwrap()
+        };
+
+        let first = revocation_list(1, Vec::new());
+        let second = revocation_list(2, Vec::from([license_key.seed.clone()]));
+
+        verifier.load_revocation_list(&second).unwrap();
+        assert_eq!(verifier.get_revocation_version(), Some(2));
+        assert_eq!(
+            verifier.validate_license_key(&license_key),
+            LicenseKeyStatus::Blacklisted
+        );
+
+        // Older revocation list is refused
+        assert_eq!(
+            verifier.load_revocation_list(&first).err(),
+            Some(LicenseError::OutdatedRevocationList {
+                version: 1,
+                current: 2
+            })
+        );
+
+        // Revocation list signed by another issuer is refused
+        let forged = LicenseRevocationList::new(
+            3,
+            LicenseDate::from_ymd(2024, 7, 1).unwrap(),
+            LicenseBlacklist::default(),
+        )
+        .sign(&LicenseSignature::generate())
+        .unwrap();
+        assert!(matches!(
+            verifier.load_revocation_list(&forged),
+            Err(LicenseError::InvalidRevocationList { .. })
+        ));
+        assert_eq!(verifier.get_revocation_version(), Some(2));
+
+        // Restored version refuses older revocation list after restart
+        let mut restarted = issuer_op
+            .derive_verifier(LicenseByteCheck::default())
+            .with_revocation_version(2);
+        assert!(restarted.load_revocation_list(&first).is_err());
+    }
+}
@@ -0,0 +1,513 @@
+//! # Offline license CLI
+//!
+//! Command-line tool for issuing, inspecting and validating license keys from license operator
+//! configuration exported by [LicenseOperatorConfig]. Operator is built with default
+//! [LicenseRegistry], so configuration has to reference built-in serializer and checksum.
+//!
+//! Exit code of `validate` and `inspect` reflects [LicenseKeyStatus], see [status_exit_code].
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use serde_json::{json, Value};
+
+use offline_license_rs::license_blacklist::LicenseBlacklist;
+use offline_license_rs::license_config::{LicenseOperatorConfig, LicenseRegistry};
+use offline_license_rs::license_expiration::{LicenseClock, LicenseExpiration, SystemLicenseClock};
+use offline_license_rs::license_key::{LicenseKeyOptions, LicenseKeyStatus};
+use offline_license_rs::license_operator::LicenseOperator;
+use offline_license_rs::LicenseKey;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Exit code of failed command, e.g. invalid configuration.
+const EXIT_ERROR: u8 = 1;
+
+#[derive(Parser)]
+#[command(name = "offline-license", version, about)]
+struct Cli {
+    /// License operator configuration, files with .json extension are read as JSON, otherwise TOML
+    #[arg(short, long, global = true, default_value = "license.toml")]
+    config: PathBuf,
+
+    /// Blacklist file of revoked seeds, checked together with blacklist of configuration
+    #[arg(short, long, global = true)]
+    blacklist: Option<PathBuf>,
+
+    /// Print machine-readable JSON output
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate license key for seed, e.g. customer email
+    Generate {
+        seed: String,
+        /// Enable feature from feature schema, can be repeated
+        #[arg(long = "feature")]
+        features: Vec<String>,
+        /// Issue date in YYYY-MM-DD format, defaults to today, requires --expires
+        #[arg(long, requires = "expires")]
+        issued: Option<String>,
+        /// Expiry date in YYYY-MM-DD format, required by configuration with expiration
+        #[arg(long)]
+        expires: Option<String>,
+    },
+    /// Validate license key
+    Validate { key: String },
+    /// Show sections of license key and result of every validation stage
+    Inspect {
+        key: String,
+        /// Show expected and actual values derived from secrets
+        #[arg(long)]
+        expose: bool,
+    },
+    /// Manage blacklist file given by --blacklist
+    Blacklist {
+        #[command(subcommand)]
+        command: BlacklistCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum BlacklistCommand {
+    /// Revoke seed of license key, revoked seed is appended to blacklist file
+    Add {
+        /// License key, or hexadecimal seed with --seed
+        value: String,
+        #[arg(long)]
+        seed: bool,
+        /// Reason of revocation
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// List revoked seeds of blacklist file
+    List,
+    /// Remove seed of license key from blacklist
+    Remove {
+        /// License key, or hexadecimal seed with --seed
+        value: String,
+        #[arg(long)]
+        seed: bool,
+    },
+}
+
+/// Result of command printed as JSON or text.
+struct Output {
+    exit_code: u8,
+    json: Value,
+    text: String,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(&cli) {
+        Ok(output) => {
+            match cli.json {
+                true => println!("{}", output.json),
+                false => println!("{}", output.text),
+            }
+            ExitCode::from(output.exit_code)
+        }
+        Err(report) => {
+            match cli.json {
+                true => println!("{}", json!({ "error": report.to_string() })),
+                false => eprintln!("error: {}", report),
+            }
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<Output> {
+    let config = LicenseOperatorConfig::load(&cli.config)?;
+    let mut operator = config.build(&LicenseRegistry::default())?;
+    if let Some(blacklist_path) = &cli.blacklist {
+        operator = operator.with_blacklist_file(blacklist_path)?;
+    }
+
+    match &cli.command {
+        Command::Generate {
+            seed,
+            features,
+            issued,
+            expires,
+        } => generate(&operator, seed, features, issued, expires),
+        Command::Validate { key } => Ok(validate(&operator, key)),
+        Command::Inspect { key, expose } => Ok(inspect(&operator, key, *expose)),
+        Command::Blacklist { command } => match &cli.blacklist {
+            Some(blacklist_path) => blacklist(blacklist_path, &mut operator, command),
+            None => {
+                Err("Cannot manage blacklist without blacklist file! [option=--blacklist]".into())
+            }
+        },
+    }
+}
+
+// ==================================================
+//                    Commands
+// ==================================================
+
+fn generate(
+    operator: &LicenseOperator,
+    seed: &str,
+    features: &[String],
+    issued: &Option<String>,
+    expires: &Option<String>,
+) -> Result<Output> {
+    let expiration = match expires {
+        Some(expires) => {
+            let issued = match issued {
+                Some(issued) => issued.parse()?,
+                None => SystemLicenseClock {}.today(),
+            };
+            Some(LicenseExpiration::new(issued, expires.parse()?)?)
+        }
+        None => None,
+    };
+
+    let options = LicenseKeyOptions {
+        expiration,
+        features: features.to_vec(),
+    };
+    let license_key = operator.generate_license_key_with_options(seed.as_bytes(), &options)?;
+    let key = operator.get_serialized_key(&license_key);
+
+    Ok(Output {
+        exit_code: 0,
+        json: json!({
+            "key": key,
+            "seed": hex::encode(&license_key.seed),
+            "features": license_key.enabled_features,
+            "expiration": expiration_json(&license_key),
+        }),
+        text: key,
+    })
+}
+
+fn validate(operator: &LicenseOperator, key: &str) -> Output {
+    let status = match operator.parse_key(key) {
+        Ok(license_key) => operator.validate_license_key(&license_key),
+        Err(_) => LicenseKeyStatus::Invalid,
+    };
+
+    Output {
+        exit_code: status_exit_code(&status),
+        json: json!({ "key": key, "status": format!("{:?}", status) }),
+        text: format!("{:?}", status),
+    }
+}
+
+fn inspect(operator: &LicenseOperator, key: &str, expose: bool) -> Output {
+    let license_key = match operator.parse_key(key) {
+        Ok(license_key) => license_key,
+        Err(report) => {
+            let status = LicenseKeyStatus::Invalid;
+            return Output {
+                exit_code: status_exit_code(&status),
+                json: json!({
+                    "key": key,
+                    "status": format!("{:?}", status),
+                    "error": report.to_string(),
+                }),
+                text: format!("status: {:?}\nerror: {}", status, report),
+            };
+        }
+    };
+
+    let report = operator.validate_license_key_detailed(&license_key, expose);
+    let status = report.status();
+
+    let sections = [
+        ("seed", &license_key.seed),
+        ("payload", &license_key.payload),
+        ("features", &license_key.features),
+        ("expiration", &license_key.expiration),
+        ("checksum", &license_key.checksum),
+        ("signature", &license_key.signature),
+    ];
+    let stages: Vec<Value> = report
+        .get_steps()
+        .iter()
+        .map(|step| {
+            json!({
+                "stage": format!("{:?}", step.stage),
+                "passed": step.passed,
+                "expected": step.expected.as_ref().map(hex::encode),
+                "actual": step.actual.as_ref().map(hex::encode),
+            })
+        })
+        .collect();
+
+    let mut text = format!("status: {:?}\n", status);
+    for (name, section) in sections.iter().filter(|(_, section)| !section.is_empty()) {
+        text.push_str(&format!("{}: {}\n", name, hex::encode(section)));
+    }
+    if !license_key.enabled_features.is_empty() {
+        text.push_str(&format!(
+            "enabled features: {}\n",
+            license_key.enabled_features.join(", ")
+        ));
+    }
+    if let Some(expiration) = license_key.get_expiration() {
+        text.push_str(&format!(
+            "issued: {}\nexpires: {}\n",
+            expiration.issued, expiration.expires
+        ));
+    }
+    for step in report.get_steps() {
+        text.push_str(&format!(
+            "{:?}: {}",
+            step.stage,
+            if step.passed { "passed" } else { "failed" }
+        ));
+        if let (Some(expected), Some(actual)) = (&step.expected, &step.actual) {
+            text.push_str(&format!(
+                " [expected={}, actual={}]",
+                hex::encode(expected),
+                hex::encode(actual)
+            ));
+        }
+        text.push('\n');
+    }
+
+    Output {
+        exit_code: status_exit_code(&status),
+        json: json!({
+            "key": key,
+            "status": format!("{:?}", status),
+            "sections": sections
+                .iter()
+                .map(|(name, section)| (name.to_string(), json!(hex::encode(section))))
+                .collect::<serde_json::Map<String, Value>>(),
+            "features": license_key.enabled_features,
+            "expiration": expiration_json(&license_key),
+            "stages": stages,
+        }),
+        text: text.trim_end().to_string(),
+    }
+}
+
+fn blacklist(
+    blacklist_path: &Path,
+    operator: &mut LicenseOperator,
+    command: &BlacklistCommand,
+) -> Result<Output> {
+    let blacklist_seed = |operator: &LicenseOperator, value: &str, seed: bool| -> Result<Vec<u8>> {
+        match seed {
+            true => Ok(hex::decode(value)?),
+            false => Ok(operator.parse_key(value)?.seed),
+        }
+    };
+
+    match command {
+        BlacklistCommand::Add {
+            value,
+            seed,
+            reason,
+        } => {
+            let seed = blacklist_seed(operator, value, *seed)?;
+            let added = !operator.get_blacklist().is_blacklisted(&seed);
+            if added {
+                operator.revoke_seed(&seed, reason)?;
+            }
+
+            Ok(Output {
+                exit_code: 0,
+                json: json!({ "seed": hex::encode(&seed), "added": added }),
+                text: hex::encode(&seed),
+            })
+        }
+        BlacklistCommand::List => {
+            let blacklist = LicenseBlacklist::load(blacklist_path)?;
+            let entries = blacklist.get_entries();
+
+            Ok(Output {
+                exit_code: 0,
+                json: json!({
+                    "blacklist": entries
+                        .iter()
+                        .map(|entry| json!({
+                            "seed": hex::encode(&entry.seed),
+                            "reason": entry.reason,
+                            "revoked_at": entry.revoked_at,
+                        }))
+                        .collect::<Vec<Value>>(),
+                }),
+                text: entries
+                    .iter()
+                    .map(|entry| hex::encode(&entry.seed))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            })
+        }
+        BlacklistCommand::Remove { value, seed } => {
+            let seed = blacklist_seed(operator, value, *seed)?;
+            let mut entries = LicenseBlacklist::load(blacklist_path)?
+                .get_entries()
+                .clone();
+            let removed = entries.iter().any(|entry| entry.seed == seed);
+            if removed {
+                entries.retain(|entry| entry.seed != seed);
+                LicenseBlacklist::from_entries(entries).save(blacklist_path)?;
+            }
+
+            Ok(Output {
+                exit_code: 0,
+                json: json!({ "seed": hex::encode(&seed), "removed": removed }),
+                text: hex::encode(&seed),
+            })
+        }
+    }
+}
+
+// ==================================================
+//                    Utilities
+// ==================================================
+
+/// Exit code of license key status, **1** is reserved for failed command.
+fn status_exit_code(status: &LicenseKeyStatus) -> u8 {
+    match status {
+        LicenseKeyStatus::Valid => 0,
+        LicenseKeyStatus::Invalid => 2,
+        LicenseKeyStatus::Blacklisted => 3,
+        LicenseKeyStatus::InvalidSignature => 4,
+        LicenseKeyStatus::Expired => 5,
+    }
+}
+
+fn expiration_json(license_key: &LicenseKey) -> Value {
+    match license_key.get_expiration() {
+        Some(expiration) => json!({
+            "issued": expiration.issued.to_string(),
+            "expires": expiration.expires.to_string(),
+        }),
+        None => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{run, status_exit_code, Cli};
+    use clap::Parser;
+    use offline_license_rs::license_config::LicenseOperatorConfig;
+    use offline_license_rs::license_key::LicenseKeyStatus;
+    use offline_license_rs::license_operator::LicenseOperator;
+    use std::path::PathBuf;
+
+    fn run_args(args: &[&str]) -> (u8, serde_json::Value) {
+        let cli = Cli::try_parse_from([&["offline-license", "--json"], args].concat()).unwrap();
+        let output = run(&cli).unwrap();
+        (output.exit_code, output.json)
+    }
+
+    #[test]
+    fn validate_status_exit_codes() {
+        assert_eq!(status_exit_code(&LicenseKeyStatus::Valid), 0);
+        assert_ne!(status_exit_code(&LicenseKeyStatus::Invalid), 0);
+        assert_ne!(status_exit_code(&LicenseKeyStatus::Blacklisted), 0);
+    }
+
+    #[test]
+    fn validate_generate_and_blacklist_commands() {
+        let config_path: PathBuf =
+            std::env::temp_dir().join(format!("offline-license-cli-{}.toml", std::process::id()));
+        let blacklist_path: PathBuf =
+            std::env::temp_dir().join(format!("offline-license-cli-{}.bin", std::process::id()));
+        let config = LicenseOperatorConfig::from_operator(&LicenseOperator::default(
+            2,
+            3,
+            [1, 2, 3, 4, 5, 6, 7, 8],
+        ))
+        .unwrap();
+        config.save(&config_path).unwrap();
+        let config_arg = config_path.to_str().unwrap();
+        let blacklist_arg = blacklist_path.to_str().unwrap();
+
+        let (exit_code, generated) = run_args(&[
+            "--config",
+            config_arg,
+            "generate",
+            "sample.name@sample.domain.com",
+        ]);
+        assert_eq!(exit_code, 0);
+        let key = generated["key"].as_str().unwrap();
+
+        let (exit_code, validated) = run_args(&["--config", config_arg, "validate", key]);
+        assert_eq!(exit_code, 0);
+        assert_eq!(validated["status"], "Valid");
+
+        // Issue date without expiry date is rejected
+        assert!(Cli::try_parse_from([
+            "offline-license",
+            "generate",
+            "sample.name@sample.domain.com",
+            "--issued",
+            "2024-01-01",
+        ])
+        .is_err());
+
+        // Blacklist commands require blacklist file
+        let cli = Cli::try_parse_from([
+            "offline-license",
+            "--config",
+            config_arg,
+            "blacklist",
+            "list",
+        ])
+        .unwrap();
+        assert!(run(&cli).is_err());
+
+        let blacklist_args = ["--config", config_arg, "--blacklist", blacklist_arg];
+        let (exit_code, added) = run_args(
+            &[
+                &blacklist_args[..],
+                &["blacklist", "add", key, "--reason", "refund"],
+            ]
+            .concat(),
+        );
+        assert_eq!(exit_code, 0);
+        assert_eq!(added["added"], true);
+        let (_, listed) = run_args(&[&blacklist_args[..], &["blacklist", "list"]].concat());
+        assert_eq!(listed["blacklist"][0]["seed"], generated["seed"]);
+        assert_eq!(listed["blacklist"][0]["reason"], "refund");
+
+        // Configuration is not modified, revoked seed is stored in blacklist file
+        assert_eq!(
+            LicenseOperatorConfig::load(&config_path)
+                .unwrap()
+                .blacklist
+                .len(),
+            0
+        );
+        let (exit_code, inspected) = run_args(&[&blacklist_args[..], &["inspect", key]].concat());
+        assert_eq!(exit_code, status_exit_code(&LicenseKeyStatus::Blacklisted));
+        assert_eq!(inspected["status"], "Blacklisted");
+
+        let seed = generated["seed"].as_str().unwrap();
+        let (_, removed) = run_args(
+            &[
+                &blacklist_args[..],
+                &["blacklist", "remove", "--seed", seed],
+            ]
+            .concat(),
+        );
+        assert_eq!(removed["removed"], true);
+        let (exit_code, _) = run_args(&[&blacklist_args[..], &["validate", key]].concat());
+        assert_eq!(exit_code, 0);
+
+        let (exit_code, validated) = run_args(&["--config", config_arg, "validate", "ABCDEF"]);
+        assert_eq!(exit_code, status_exit_code(&LicenseKeyStatus::Invalid));
+        assert_eq!(validated["status"], "Invalid");
+
+        std::fs::remove_file(config_path).unwrap();
+        std::fs::remove_file(blacklist_path).unwrap();
+    }
+}
@@ -2,11 +2,8 @@ use color_eyre::eyre::eyre;
 use color_eyre::Report;
 use log::{info, LevelFilter};
 use offline_license_rs::adler32::adler32_checksum;
-use offline_license_rs::license_blacklist::LicenseBlacklist;
-use offline_license_rs::license_byte_check::LicenseByteCheck;
 use offline_license_rs::license_checksum::LicenseChecksum;
 use simplelog::{ColorChoice, Config, TermLogger, TerminalMode};
-use std::borrow::Borrow;
 use std::num::Wrapping;
 
 use offline_license_rs::license_key::LicenseKeyStatus;
@@ -14,6 +11,7 @@ use offline_license_rs::license_magic::LicenseMagic;
 use offline_license_rs::license_operator::LicenseOperator;
 use offline_license_rs::license_properties::LicenseProperties;
 use offline_license_rs::license_serializer::LicenseKeySerializer;
+use offline_license_rs::LicenseError;
 
 pub struct CustomizedLicenseKeySerializer {}
 
@@ -42,8 +40,8 @@ impl LicenseKeySerializer for CustomizedLicenseKeySerializer {
     }
 
     #[inline(always)]
-    fn deserialize_key(&self, key: String) -> Vec<u8> {
-        key.into_bytes()
+    fn deserialize_key(&self, key: String) -> Result<Vec<u8>, LicenseError> {
+        Ok(hex::decode(key)?)
     }
 
     #[inline(always)]
@@ -69,10 +67,11 @@ fn main() -> Result<(), Report> {
 
     let user_email = "sample.name@sample.domain.com";
 
+    // Four magic of four bytes each
     let license_properties = LicenseProperties {
         key_size: 32,
-        magic_size: 3,
-        magic_count: 2,
+        magic_size: 4,
+        magic_count: 4,
     };
 
     let license_magic = LicenseMagic::new(Vec::from([
@@ -86,22 +85,18 @@ fn main() -> Result<(), Report> {
         Vec::from([0xFF, 0xAA, 0x24, 0xEA, 0x12, 0x44, 0x3F, 0xF8]),
         4,
         adler32_checksum,
-    );
-
-    // Create empty blacklist
-    let license_blacklist = LicenseBlacklist::default();
-
-    let license_byte_check =
-        LicenseByteCheck::new(Vec::from([0, 3]), license_magic.borrow()).unwrap();
-
-    let license_op = LicenseOperator::new(
-        license_properties,
-        license_magic,
-        Box::new(CustomizedLicenseKeySerializer {}),
-        license_checksum,
-        license_blacklist,
-        license_byte_check,
-    );
+    )
+    .with_name("adler32");
+
+    // Builder validates that components fit together, blacklist is empty by default
+    let license_op = LicenseOperator::builder()
+        .with_properties(license_properties)
+        .with_magic(license_magic)
+        .with_serializer(Box::new(CustomizedLicenseKeySerializer {}))
+        .with_checksum(license_checksum)
+        .with_byte_check(Vec::from([0, 3]))
+        .build()
+        .map_err(|report| eyre!(report.to_string()))?;
 
     match license_op.generate_license_key(user_email.as_bytes()) {
         Ok(valid) => {
@@ -120,6 +115,12 @@ fn main() -> Result<(), Report> {
                 LicenseKeyStatus::Blacklisted => {
                     info!("Blacklisted key")
                 }
+                LicenseKeyStatus::InvalidSignature => {
+                    info!("Invalid signature of key")
+                }
+                LicenseKeyStatus::Expired => {
+                    info!("Expired key")
+                }
             }
         }
         Err(report) => return Err(eyre!(report.to_string())),
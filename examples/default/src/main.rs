@@ -38,6 +38,12 @@ fn main() -> Result<(), Report> {
                 LicenseKeyStatus::Blacklisted => {
                     info!("Blacklisted key")
                 }
+                LicenseKeyStatus::InvalidSignature => {
+                    info!("Invalid signature of key")
+                }
+                LicenseKeyStatus::Expired => {
+                    info!("Expired key")
+                }
             }
         }
         Err(report) => return Err(eyre!(report.to_string())),
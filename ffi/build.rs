@@ -0,0 +1,17 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Generate C header of exported functions into `OUT_DIR`, committed header
+/// `include/offline_license.h` is compared with generated one by `tests/header.rs`.
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("Unable to generate C header")
+        .write_to_file(out_dir.join("offline_license.h"));
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,112 @@
+/* Generated by cbindgen from offline_license_rs_ffi, do not edit. */
+
+#ifndef OFFLINE_LICENSE_H
+#define OFFLINE_LICENSE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Status of license key or error of called function.
+typedef enum OfflineLicenseStatus {
+  OFFLINE_LICENSE_STATUS_VALID = 0,
+  OFFLINE_LICENSE_STATUS_INVALID = 1,
+  OFFLINE_LICENSE_STATUS_BLACKLISTED = 2,
+  OFFLINE_LICENSE_STATUS_INVALID_SIGNATURE = 3,
+  OFFLINE_LICENSE_STATUS_EXPIRED = 4,
+  // Null pointer or string which is not valid UTF-8.
+  OFFLINE_LICENSE_STATUS_INVALID_ARGUMENT = -1,
+  // Configuration cannot be parsed or built into license operator.
+  OFFLINE_LICENSE_STATUS_INVALID_CONFIG = -2,
+  // Library panicked, panic is caught instead of unwinding across C ABI.
+  OFFLINE_LICENSE_STATUS_INTERNAL_ERROR = -3,
+} OfflineLicenseStatus;
+
+// Opaque handle of parsed license key.
+typedef struct OfflineLicenseKey OfflineLicenseKey;
+
+// Opaque handle of license operator.
+typedef struct OfflineLicenseOperator OfflineLicenseOperator;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Build license operator from TOML configuration, operator is written to
+// **license_operator** and has to be released by [offline_license_operator_free].
+//
+// # Safety
+//
+// **config** has to be null or null terminated string, **license_operator** has to be null or
+// valid pointer.
+enum OfflineLicenseStatus offline_license_operator_from_toml(const char *config,
+                                                             struct OfflineLicenseOperator **license_operator);
+
+// Build license operator from JSON configuration, operator is written to
+// **license_operator** and has to be released by [offline_license_operator_free].
+//
+// # Safety
+//
+// **config** has to be null or null terminated string, **license_operator** has to be null or
+// valid pointer.
+enum OfflineLicenseStatus offline_license_operator_from_json(const char *config,
+                                                             struct OfflineLicenseOperator **license_operator);
+
+// Release license operator, null is ignored.
+//
+// # Safety
+//
+// **license_operator** has to be null or created by `offline_license_operator_from_*` and not
+// released yet.
+void offline_license_operator_free(struct OfflineLicenseOperator *license_operator);
+
+// Parse license key typed by user, license key is written to **license_key** and has to be
+// released by [offline_license_key_free]. License key which cannot be parsed is invalid.
+//
+// # Safety
+//
+// **license_operator** has to be null or live operator handle, **key** null or null terminated
+// string and **license_key** null or valid pointer.
+enum OfflineLicenseStatus offline_license_parse_key(const struct OfflineLicenseOperator *license_operator,
+                                                    const char *key,
+                                                    struct OfflineLicenseKey **license_key);
+
+// Validate parsed license key.
+//
+// # Safety
+//
+// **license_operator** and **license_key** have to be null or live handles.
+enum OfflineLicenseStatus offline_license_validate_key(const struct OfflineLicenseOperator *license_operator,
+                                                       const struct OfflineLicenseKey *license_key);
+
+// Parse and validate license key typed by user, license key which cannot be parsed is invalid.
+//
+// # Safety
+//
+// **license_operator** has to be null or live operator handle and **key** null or null
+// terminated string.
+enum OfflineLicenseStatus offline_license_validate(const struct OfflineLicenseOperator *license_operator,
+                                                   const char *key);
+
+// Check whether feature is enabled by license key, license key should be validated first.
+//
+// # Safety
+//
+// **license_key** has to be null or live license key handle and **name** null or null
+// terminated string.
+bool offline_license_key_has_feature(const struct OfflineLicenseKey *license_key, const char *name);
+
+// Release license key, null is ignored.
+//
+// # Safety
+//
+// **license_key** has to be null or created by [offline_license_parse_key] and not released
+// yet.
+void offline_license_key_free(struct OfflineLicenseKey *license_key);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* OFFLINE_LICENSE_H */
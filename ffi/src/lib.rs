@@ -0,0 +1,279 @@
+//! # Offline license FFI
+//!
+//! C ABI validating license keys from C and C++ applications, header `include/offline_license.h`
+//! is generated by [cbindgen](https://github.com/mozilla/cbindgen).
+//!
+//! License operator and parsed license key are opaque handles created by `offline_license_*`
+//! functions and released by their free functions. Every function reports result as
+//! [OfflineLicenseStatus], which mirrors [LicenseKeyStatus] with negative error codes. Panics are
+//! caught and reported as [InternalError](OfflineLicenseStatus::InternalError).
+//!
+//! Operator is built as [verifier](LicenseOperatorConfig::build_verifier) with default
+//! [LicenseRegistry] from configuration exported by [LicenseOperatorConfig], ship configuration
+//! of verifier ([from_verifier](LicenseOperatorConfig::from_verifier)) with your application.
+
+use std::ffi::{c_char, CStr};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use offline_license_rs::license_config::{LicenseOperatorConfig, LicenseRegistry};
+use offline_license_rs::license_key::LicenseKeyStatus;
+use offline_license_rs::license_verifier::LicenseVerifier;
+use offline_license_rs::{LicenseError, LicenseKey};
+
+type Result<T> = std::result::Result<T, LicenseError>;
+
+/// Status of license key or error of called function.
+#[repr(C)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum OfflineLicenseStatus {
+    Valid = 0,
+    Invalid = 1,
+    Blacklisted = 2,
+    InvalidSignature = 3,
+    Expired = 4,
+    /// Null pointer or string which is not valid UTF-8.
+    InvalidArgument = -1,
+    /// Configuration cannot be parsed or built into license operator.
+    InvalidConfig = -2,
+    /// Library panicked, panic is caught instead of unwinding across C ABI.
+    InternalError = -3,
+}
+
+impl From<LicenseKeyStatus> for OfflineLicenseStatus {
+    fn from(status: LicenseKeyStatus) -> Self {
+        match status {
+            LicenseKeyStatus::Valid => OfflineLicenseStatus::Valid,
+            LicenseKeyStatus::Invalid => OfflineLicenseStatus::Invalid,
+            LicenseKeyStatus::Blacklisted => OfflineLicenseStatus::Blacklisted,
+            LicenseKeyStatus::InvalidSignature => OfflineLicenseStatus::InvalidSignature,
+            LicenseKeyStatus::Expired => OfflineLicenseStatus::Expired,
+        }
+    }
+}
+
+/// Opaque handle of license operator.
+pub struct OfflineLicenseOperator {
+    verifier: LicenseVerifier,
+}
+
+/// Opaque handle of parsed license key.
+pub struct OfflineLicenseKey {
+    key: LicenseKey,
+}
+
+/// Build license operator from TOML configuration, operator is written to
+/// **license_operator** and has to be released by [offline_license_operator_free].
+///
+/// # Safety
+///
+/// **config** has to be null or null terminated string, **license_operator** has to be null or
+/// valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_operator_from_toml(
+    config: *const c_char,
+    license_operator: *mut *mut OfflineLicenseOperator,
+) -> OfflineLicenseStatus {
+    build_operator(config, license_operator, LicenseOperatorConfig::from_toml)
+}
+
+/// Build license operator from JSON configuration, operator is written to
+/// **license_operator** and has to be released by [offline_license_operator_free].
+///
+/// # Safety
+///
+/// **config** has to be null or null terminated string, **license_operator** has to be null or
+/// valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_operator_from_json(
+    config: *const c_char,
+    license_operator: *mut *mut OfflineLicenseOperator,
+) -> OfflineLicenseStatus {
+    build_operator(config, license_operator, LicenseOperatorConfig::from_json)
+}
+
+/// Release license operator, null is ignored.
+///
+/// # Safety
+///
+/// **license_operator** has to be null or created by `offline_license_operator_from_*` and not
+/// released yet.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_operator_free(
+    license_operator: *mut OfflineLicenseOperator,
+) {
+    catch_panic((), || {
+        if !license_operator.is_null() {
+            drop(Box::from_raw(license_operator));
+        }
+    })
+}
+
+/// Parse license key typed by user, license key is written to **license_key** and has to be
+/// released by [offline_license_key_free]. License key which cannot be parsed is invalid.
+///
+/// # Safety
+///
+/// **license_operator** has to be null or live operator handle, **key** null or null terminated
+/// string and **license_key** null or valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_parse_key(
+    license_operator: *const OfflineLicenseOperator,
+    key: *const c_char,
+    license_key: *mut *mut OfflineLicenseKey,
+) -> OfflineLicenseStatus {
+    catch_panic(OfflineLicenseStatus::InternalError, || {
+        let (Some(operator), Some(key)) = (license_operator.as_ref(), to_str(key)) else {
+            return OfflineLicenseStatus::InvalidArgument;
+        };
+        if license_key.is_null() {
+            return OfflineLicenseStatus::InvalidArgument;
+        }
+
+        *license_key = ptr::null_mut();
+        match operator.verifier.parse_key(key) {
+            Ok(key) => {
+                *license_key = Box::into_raw(Box::new(OfflineLicenseKey { key }));
+                OfflineLicenseStatus::Valid
+            }
+            Err(_) => OfflineLicenseStatus::Invalid,
+        }
+    })
+}
+
+/// Validate parsed license key.
+///
+/// # Safety
+///
+/// **license_operator** and **license_key** have to be null or live handles.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_validate_key(
+    license_operator: *const OfflineLicenseOperator,
+    license_key: *const OfflineLicenseKey,
+) -> OfflineLicenseStatus {
+    catch_panic(OfflineLicenseStatus::InternalError, || {
+        match (license_operator.as_ref(), license_key.as_ref()) {
+            (Some(operator), Some(license_key)) => operator
+                .verifier
+                .validate_license_key(&license_key.key)
+                .into(),
+            _ => OfflineLicenseStatus::InvalidArgument,
+        }
+    })
+}
+
+/// Parse and validate license key typed by user, license key which cannot be parsed is invalid.
+///
+/// # Safety
+///
+/// **license_operator** has to be null or live operator handle and **key** null or null
+/// terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_validate(
+    license_operator: *const OfflineLicenseOperator,
+    key: *const c_char,
+) -> OfflineLicenseStatus {
+    catch_panic(OfflineLicenseStatus::InternalError, || {
+        let (Some(operator), Some(key)) = (license_operator.as_ref(), to_str(key)) else {
+            return OfflineLicenseStatus::InvalidArgument;
+        };
+
+        match operator.verifier.parse_key(key) {
+            Ok(license_key) => operator.verifier.validate_license_key(&license_key).into(),
+            Err(_) => OfflineLicenseStatus::Invalid,
+        }
+    })
+}
+
+/// Check whether feature is enabled by license key, license key should be validated first.
+///
+/// # Safety
+///
+/// **license_key** has to be null or live license key handle and **name** null or null
+/// terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_key_has_feature(
+    license_key: *const OfflineLicenseKey,
+    name: *const c_char,
+) -> bool {
+    catch_panic(false, || match (license_key.as_ref(), to_str(name)) {
+        (Some(license_key), Some(name)) => license_key.key.has_feature(name),
+        _ => false,
+    })
+}
+
+/// Release license key, null is ignored.
+///
+/// # Safety
+///
+/// **license_key** has to be null or created by [offline_license_parse_key] and not released
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn offline_license_key_free(license_key: *mut OfflineLicenseKey) {
+    catch_panic((), || {
+        if !license_key.is_null() {
+            drop(Box::from_raw(license_key));
+        }
+    })
+}
+
+unsafe fn build_operator(
+    config: *const c_char,
+    license_operator: *mut *mut OfflineLicenseOperator,
+    parse: fn(&str) -> Result<LicenseOperatorConfig>,
+) -> OfflineLicenseStatus {
+    catch_panic(OfflineLicenseStatus::InternalError, || {
+        let Some(config) = to_str(config) else {
+            return OfflineLicenseStatus::InvalidArgument;
+        };
+        if license_operator.is_null() {
+            return OfflineLicenseStatus::InvalidArgument;
+        }
+
+        *license_operator = ptr::null_mut();
+        match parse(config).and_then(|config| config.build_verifier(&LicenseRegistry::default())) {
+            Ok(built) => {
+                *license_operator =
+                    Box::into_raw(Box::new(OfflineLicenseOperator { verifier: built }));
+                OfflineLicenseStatus::Valid
+            }
+            Err(_) => OfflineLicenseStatus::InvalidConfig,
+        }
+    })
+}
+
+/// Run body of exported function, panic is returned as **fallback** instead of unwinding across
+/// C ABI.
+fn catch_panic<T>(fallback: T, body: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or(fallback)
+}
+
+/// Borrow null terminated string, none for null pointer or invalid UTF-8.
+unsafe fn to_str<'a>(string: *const c_char) -> Option<&'a str> {
+    match string.is_null() {
+        true => None,
+        false => CStr::from_ptr(string).to_str().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{catch_panic, OfflineLicenseStatus};
+
+    #[test]
+    fn validate_catch_panic() {
+        assert_eq!(
+            catch_panic(OfflineLicenseStatus::InternalError, || {
+                OfflineLicenseStatus::Valid
+            }),
+            OfflineLicenseStatus::Valid
+        );
+        assert_eq!(
+            catch_panic(
+                OfflineLicenseStatus::InternalError,
+                || -> OfflineLicenseStatus { panic!("panic inside library") }
+            ),
+            OfflineLicenseStatus::InternalError
+        );
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Validation of license keys through C ABI, compiled and run by tests/c_api.rs.
+ *
+ * Usage: validate_license <config.toml> <valid key> <blacklisted key>
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "offline_license.h"
+
+#define CHECK(condition)                                                                \
+    do {                                                                                \
+        if (!(condition)) {                                                             \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+            return 1;                                                                   \
+        }                                                                               \
+    } while (0)
+
+static char *read_file(const char *path) {
+    FILE *file = fopen(path, "rb");
+    if (file == NULL) {
+        return NULL;
+    }
+
+    fseek(file, 0, SEEK_END);
+    long size = ftell(file);
+    fseek(file, 0, SEEK_SET);
+
+    char *content = malloc(size + 1);
+    if (content != NULL) {
+        content[fread(content, 1, size, file)] = '\0';
+    }
+    fclose(file);
+
+    return content;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 4) {
+        fprintf(stderr, "usage: %s <config.toml> <valid key> <blacklisted key>\n", argv[0]);
+        return 2;
+    }
+
+    char *config = read_file(argv[1]);
+    CHECK(config != NULL);
+
+    OfflineLicenseOperator *license_operator = NULL;
+    CHECK(offline_license_operator_from_toml(config, &license_operator) ==
+          OFFLINE_LICENSE_STATUS_VALID);
+    free(config);
+
+    /* Error codes are negative and distinct from license key statuses */
+    CHECK(OFFLINE_LICENSE_STATUS_INTERNAL_ERROR < 0);
+    CHECK(OFFLINE_LICENSE_STATUS_INTERNAL_ERROR != OFFLINE_LICENSE_STATUS_INVALID_ARGUMENT);
+    CHECK(OFFLINE_LICENSE_STATUS_INTERNAL_ERROR != OFFLINE_LICENSE_STATUS_INVALID_CONFIG);
+
+    OfflineLicenseOperator *invalid_operator = NULL;
+    CHECK(offline_license_operator_from_toml("magic = 1", &invalid_operator) ==
+          OFFLINE_LICENSE_STATUS_INVALID_CONFIG);
+    CHECK(invalid_operator == NULL);
+
+    /* Parse and validate typed license key */
+    CHECK(offline_license_validate(license_operator, argv[2]) == OFFLINE_LICENSE_STATUS_VALID);
+    CHECK(offline_license_validate(license_operator, argv[3]) ==
+          OFFLINE_LICENSE_STATUS_BLACKLISTED);
+    CHECK(offline_license_validate(license_operator, "not a license key") ==
+          OFFLINE_LICENSE_STATUS_INVALID);
+    CHECK(offline_license_validate(NULL, argv[2]) == OFFLINE_LICENSE_STATUS_INVALID_ARGUMENT);
+    CHECK(offline_license_validate(license_operator, NULL) ==
+          OFFLINE_LICENSE_STATUS_INVALID_ARGUMENT);
+
+    /* Parsed license key handle */
+    OfflineLicenseKey *license_key = NULL;
+    CHECK(offline_license_parse_key(license_operator, argv[2], &license_key) ==
+          OFFLINE_LICENSE_STATUS_VALID);
+    CHECK(offline_license_validate_key(license_operator, license_key) ==
+          OFFLINE_LICENSE_STATUS_VALID);
+    CHECK(offline_license_key_has_feature(license_key, "export_pdf"));
+    CHECK(!offline_license_key_has_feature(license_key, "unknown"));
+    offline_license_key_free(license_key);
+
+    OfflineLicenseKey *invalid_key = NULL;
+    CHECK(offline_license_parse_key(license_operator, "not a license key", &invalid_key) ==
+          OFFLINE_LICENSE_STATUS_INVALID);
+    CHECK(invalid_key == NULL);
+
+    offline_license_key_free(NULL);
+    offline_license_operator_free(license_operator);
+
+    return 0;
+}
@@ -0,0 +1,85 @@
+//! Compile C test program `tests/c/validate_license.c` against generated header and shared
+//! library, then run it with license keys issued by Rust API.
+
+#![cfg(unix)]
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use offline_license_rs::license_byte_check::LicenseByteCheck;
+use offline_license_rs::license_config::LicenseOperatorConfig;
+use offline_license_rs::license_features::LicenseFeatures;
+use offline_license_rs::license_key::LicenseKeyOptions;
+use offline_license_rs::license_operator::LicenseOperator;
+
+fn generate_key(issuer_op: &LicenseOperator, seed: &str) -> String {
+    let options = LicenseKeyOptions {
+        features: Vec::from(["export_pdf".to_string()]),
+        ..Default::default()
+    };
+    let license_key = issuer_op
+        .generate_license_key_with_options(seed.as_bytes(), &options)
+        .unwrap();
+    issuer_op.get_serialized_key(&license_key)
+}
+
+/// Compile C test program linked to shared library next to test executable.
+fn compile_c_program(output: &Path) {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let library_dir = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(crate_dir.join("tests/c/validate_license.c"))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg("-o")
+        .arg(output)
+        .arg("-L")
+        .arg(&library_dir)
+        .arg("-loffline_license_rs_ffi")
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .status()
+        .unwrap();
+
+    assert!(status.success(), "C test program failed to compile");
+}
+
+#[test]
+fn validate_c_api() {
+    let mut issuer_op = LicenseOperator::default(3, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+        .with_features(LicenseFeatures::new(Vec::from(["export_pdf".to_string()])).unwrap());
+    let key = generate_key(&issuer_op, "sample.name@sample.domain.com");
+    let blacklisted_key = generate_key(&issuer_op, "cracker@sample.domain.com");
+    let blacklisted_seed = issuer_op.parse_key(&blacklisted_key).unwrap().seed;
+    issuer_op.add_seed_to_blacklist(&blacklisted_seed);
+
+    let byte_check = LicenseByteCheck::new(Vec::from([0, 2]), issuer_op.get_magic()).unwrap();
+    let config =
+        LicenseOperatorConfig::from_verifier(&issuer_op.derive_verifier(byte_check)).unwrap();
+
+    let output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    let config_path = output_dir.join("c_api_verifier.toml");
+    config.save(&config_path).unwrap();
+
+    let program = output_dir.join("validate_license");
+    compile_c_program(&program);
+
+    let output = Command::new(&program)
+        .arg(&config_path)
+        .arg(&key)
+        .arg(&blacklisted_key)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::remove_file(config_path).unwrap();
+}
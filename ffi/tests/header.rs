@@ -0,0 +1,14 @@
+//! Committed C header `include/offline_license.h` has to match header generated by cbindgen, so C
+//! applications can be built without Rust toolchain.
+
+#[test]
+fn validate_committed_header() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/offline_license.h"));
+    let committed = include_str!("../include/offline_license.h");
+
+    assert!(
+        generated == committed,
+        "C header is outdated, copy {}/offline_license.h to ffi/include/offline_license.h",
+        env!("OUT_DIR")
+    );
+}
@@ -0,0 +1,493 @@
+//! # Offline license Python
+//!
+//! Python module `offline_license` issuing license keys in back-office with the same license
+//! operator as Rust API. Operator is loaded from configuration exported by
+//! [LicenseOperatorConfig] and built with default [LicenseRegistry], so configuration has to
+//! reference built-in serializer and checksum.
+//!
+//! Seeds are revoked by [revoke_seed](LicenseOperator::revoke_seed) with revocation reason and
+//! appended to optional blacklist file, same as `--blacklist` file of `offline-license` tool.
+//! Loaded configuration is never modified by revocation.
+
+use std::path::{Path, PathBuf};
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use offline_license_rs::license_blacklist::LicenseBlacklist;
+use offline_license_rs::license_config::{LicenseOperatorConfig, LicenseRegistry};
+use offline_license_rs::license_expiration::{
+    LicenseClock, LicenseDate, LicenseExpiration, SystemLicenseClock,
+};
+use offline_license_rs::license_key::{self, LicenseKeyOptions};
+use offline_license_rs::license_operator::LicenseOperator;
+use offline_license_rs::{LicenseError, LicenseKey};
+
+create_exception!(
+    offline_license,
+    PyLicenseError,
+    PyException,
+    "Error of license operator."
+);
+
+/// Status of validated license key, see [LicenseKeyStatus](license_key::LicenseKeyStatus).
+#[pyclass(name = "LicenseKeyStatus", eq, eq_int, module = "offline_license")]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum PyLicenseKeyStatus {
+    Valid,
+    Invalid,
+    Blacklisted,
+    InvalidSignature,
+    Expired,
+}
+
+impl From<license_key::LicenseKeyStatus> for PyLicenseKeyStatus {
+    fn from(status: license_key::LicenseKeyStatus) -> Self {
+        match status {
+            license_key::LicenseKeyStatus::Valid => PyLicenseKeyStatus::Valid,
+            license_key::LicenseKeyStatus::Invalid => PyLicenseKeyStatus::Invalid,
+            license_key::LicenseKeyStatus::Blacklisted => PyLicenseKeyStatus::Blacklisted,
+            license_key::LicenseKeyStatus::InvalidSignature => PyLicenseKeyStatus::InvalidSignature,
+            license_key::LicenseKeyStatus::Expired => PyLicenseKeyStatus::Expired,
+        }
+    }
+}
+
+/// License key generated or parsed by [PyLicenseOperator].
+#[pyclass(name = "LicenseKey", module = "offline_license")]
+pub struct PyLicenseKey {
+    key: LicenseKey,
+    serialized_key: String,
+}
+
+#[pymethods]
+impl PyLicenseKey {
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    fn has_feature(&self, name: &str) -> bool {
+        self.key.has_feature(name)
+    }
+
+    fn __str__(&self) -> &str {
+        &self.serialized_key
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    /// Serialized license key handed to customer.
+    #[getter]
+    fn key(&self) -> &str {
+        &self.serialized_key
+    }
+
+    /// Seed hash identifying license key in blacklist.
+    #[getter]
+    fn seed(&self) -> &[u8] {
+        &self.key.seed
+    }
+
+    #[getter]
+    fn generation(&self) -> Option<u8> {
+        self.key.get_generation()
+    }
+
+    #[getter]
+    fn features(&self) -> Vec<String> {
+        self.key.enabled_features.clone()
+    }
+
+    /// Issue date formatted as `YYYY-MM-DD`, none when license key does not expire.
+    #[getter]
+    fn issued(&self) -> Option<String> {
+        self.key
+            .get_expiration()
+            .map(|expiration| expiration.issued.to_string())
+    }
+
+    /// Expiry date formatted as `YYYY-MM-DD`, none when license key does not expire.
+    #[getter]
+    fn expires(&self) -> Option<String> {
+        self.key
+            .get_expiration()
+            .map(|expiration| expiration.expires.to_string())
+    }
+}
+
+/// License operator issuing license keys, built from exported configuration.
+#[pyclass(name = "LicenseOperator", module = "offline_license", unsendable)]
+pub struct PyLicenseOperator {
+    config: LicenseOperatorConfig,
+    operator: LicenseOperator,
+}
+
+impl PyLicenseOperator {
+    fn build(config: LicenseOperatorConfig, blacklist: Option<PathBuf>) -> PyResult<Self> {
+        let operator = Self::build_operator(&config, blacklist.as_deref())?;
+
+        Ok(PyLicenseOperator { config, operator })
+    }
+
+    /// Operator of **config** checking blacklist of configuration and blacklist file.
+    fn build_operator(
+        config: &LicenseOperatorConfig,
+        blacklist: Option<&Path>,
+    ) -> PyResult<LicenseOperator> {
+        let operator = config
+            .build(&LicenseRegistry::default())
+            .map_err(license_error)?;
+
+        match blacklist {
+            Some(blacklist) => operator
+                .with_blacklist_file(blacklist)
+                .map_err(license_error),
+            None => Ok(operator),
+        }
+    }
+
+    fn license_key(&self, key: LicenseKey) -> PyLicenseKey {
+        PyLicenseKey {
+            serialized_key: self.operator.get_serialized_key(&key),
+            key,
+        }
+    }
+}
+
+#[pymethods]
+impl PyLicenseOperator {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    /// Build operator from TOML configuration, seeds are revoked to optional **blacklist** file.
+    #[staticmethod]
+    #[pyo3(signature = (config, blacklist = None))]
+    fn from_toml(config: &str, blacklist: Option<PathBuf>) -> PyResult<Self> {
+        Self::build(
+            LicenseOperatorConfig::from_toml(config).map_err(license_error)?,
+            blacklist,
+        )
+    }
+
+    /// Build operator from JSON configuration, seeds are revoked to optional **blacklist** file.
+    #[staticmethod]
+    #[pyo3(signature = (config, blacklist = None))]
+    fn from_json(config: &str, blacklist: Option<PathBuf>) -> PyResult<Self> {
+        Self::build(
+            LicenseOperatorConfig::from_json(config).map_err(license_error)?,
+            blacklist,
+        )
+    }
+
+    /// Load configuration from file, files with **.json** extension are parsed as JSON and any
+    /// other file as TOML. Seeds are revoked to optional **blacklist** file.
+    #[staticmethod]
+    #[pyo3(signature = (path, blacklist = None))]
+    fn load(path: PathBuf, blacklist: Option<PathBuf>) -> PyResult<Self> {
+        Self::build(
+            LicenseOperatorConfig::load(&path).map_err(license_error)?,
+            blacklist,
+        )
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    /// Generate license key for **seed** with enabled **features**. License key expires at
+    /// **expires** (`YYYY-MM-DD`) when operator has expiration, issue date defaults to today and
+    /// cannot be set without expiry date.
+    #[pyo3(signature = (seed, features = Vec::new(), expires = None, issued = None))]
+    fn generate_license_key(
+        &self,
+        seed: &[u8],
+        features: Vec<String>,
+        expires: Option<&str>,
+        issued: Option<&str>,
+    ) -> PyResult<PyLicenseKey> {
+        let expiration = match expires {
+            Some(expires) => {
+                let issued = match issued {
+                    Some(issued) => parse_date(issued)?,
+                    None => SystemLicenseClock {}.today(),
+                };
+                Some(LicenseExpiration::new(issued, parse_date(expires)?).map_err(license_error)?)
+            }
+            None if issued.is_some() => {
+                return Err(PyLicenseError::new_err(
+                    "Cannot set issue date of license key without expiry date!",
+                ))
+            }
+            None => None,
+        };
+
+        let options = LicenseKeyOptions {
+            expiration,
+            features,
+        };
+        let key = self
+            .operator
+            .generate_license_key_with_options(seed, &options)
+            .map_err(license_error)?;
+
+        Ok(self.license_key(key))
+    }
+
+    fn parse_key(&self, key: &str) -> PyResult<PyLicenseKey> {
+        let key = self.operator.parse_key(key).map_err(license_error)?;
+        Ok(self.license_key(key))
+    }
+
+    /// Validate serialized license key, license key which cannot be parsed is invalid.
+    fn validate_license_key(&self, key: &str) -> PyLicenseKeyStatus {
+        match self.operator.parse_key(key) {
+            Ok(license_key) => self.operator.validate_license_key(&license_key).into(),
+            Err(_) => PyLicenseKeyStatus::Invalid,
+        }
+    }
+
+    /// Revoke seed of license key with revocation **reason**, returns false when seed is already
+    /// blacklisted. Revoked seed is appended to blacklist file when given.
+    #[pyo3(signature = (seed, reason = ""))]
+    fn add_seed_to_blacklist(&mut self, seed: &[u8], reason: &str) -> PyResult<bool> {
+        if self.operator.get_blacklist().is_blacklisted(seed) {
+            return Ok(false);
+        }
+
+        self.operator
+            .revoke_seed(seed, reason)
+            .map_err(license_error)?;
+        Ok(true)
+    }
+
+    /// Remove seed of license key from blacklist file, returns false when seed is not in
+    /// blacklist file.
+    fn remove_seed_from_blacklist(&mut self, seed: &[u8]) -> PyResult<bool> {
+        let Some(blacklist_path) = self.operator.get_blacklist_path().map(Path::to_path_buf) else {
+            return Err(PyLicenseError::new_err(
+                "Cannot remove seed from blacklist without blacklist file!",
+            ));
+        };
+
+        let mut entries = LicenseBlacklist::load(&blacklist_path)
+            .map_err(license_error)?
+            .get_entries()
+            .clone();
+        if !entries.iter().any(|entry| entry.seed == seed) {
+            return Ok(false);
+        }
+
+        entries.retain(|entry| entry.seed != seed);
+        LicenseBlacklist::from_entries(entries)
+            .save(&blacklist_path)
+            .map_err(license_error)?;
+        self.operator = Self::build_operator(&self.config, Some(&blacklist_path))?;
+        Ok(true)
+    }
+
+    /// Revoke seed of serialized license key with revocation **reason**, returns revoked seed.
+    #[pyo3(signature = (key, reason = ""))]
+    fn revoke_key<'py>(
+        &mut self,
+        py: Python<'py>,
+        key: &str,
+        reason: &str,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let seed = self.operator.parse_key(key).map_err(license_error)?.seed;
+        self.add_seed_to_blacklist(&seed, reason)?;
+        Ok(PyBytes::new(py, &seed))
+    }
+
+    /// Save configuration, format is chosen by file extension same as in
+    /// [load](PyLicenseOperator::load).
+    fn save(&self, path: PathBuf) -> PyResult<()> {
+        self.config.save(&path).map_err(license_error)
+    }
+
+    fn to_toml(&self) -> PyResult<String> {
+        self.config.to_toml().map_err(license_error)
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        self.config.to_json().map_err(license_error)
+    }
+
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    /// Blacklisted seeds of configuration and blacklist file.
+    #[getter]
+    fn blacklist<'py>(&self, py: Python<'py>) -> Vec<Bound<'py, PyBytes>> {
+        self.operator
+            .get_blacklist()
+            .entries()
+            .into_iter()
+            .flatten()
+            .map(|entry| PyBytes::new(py, &entry.seed))
+            .collect()
+    }
+
+    /// Entries of blacklist as `(seed, reason, revoked_at)` tuples, revocation time is in seconds
+    /// since Unix epoch.
+    #[getter]
+    fn blacklist_entries<'py>(&self, py: Python<'py>) -> Vec<(Bound<'py, PyBytes>, String, u64)> {
+        self.operator
+            .get_blacklist()
+            .entries()
+            .into_iter()
+            .flatten()
+            .map(|entry| {
+                (
+                    PyBytes::new(py, &entry.seed),
+                    entry.reason.clone(),
+                    entry.revoked_at,
+                )
+            })
+            .collect()
+    }
+}
+
+#[pymodule]
+fn offline_license(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyLicenseOperator>()?;
+    m.add_class::<PyLicenseKey>()?;
+    m.add_class::<PyLicenseKeyStatus>()?;
+    m.add("LicenseError", m.py().get_type::<PyLicenseError>())?;
+    Ok(())
+}
+
+// ==================================================
+//                    Utilities
+// ==================================================
+
+#[inline(always)]
+fn license_error(error: LicenseError) -> PyErr {
+    PyLicenseError::new_err(error.to_string())
+}
+
+/// Parse date formatted as `YYYY-MM-DD`.
+#[inline(always)]
+fn parse_date(date: &str) -> PyResult<LicenseDate> {
+    date.parse().map_err(license_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::offline_license;
+    use offline_license_rs::license_checksum::LicenseChecksum;
+    use offline_license_rs::license_config::LicenseOperatorConfig;
+    use offline_license_rs::license_expiration::SystemLicenseClock;
+    use offline_license_rs::license_features::LicenseFeatures;
+    use offline_license_rs::license_magic::LicenseMagic;
+    use offline_license_rs::license_operator::LicenseOperator;
+    use offline_license_rs::license_properties::LicenseProperties;
+    use offline_license_rs::license_serializer::DefaultLicenseKeySerializer;
+    use pyo3::ffi::c_str;
+    use pyo3::prelude::*;
+    use pyo3::types::{PyDict, PyModule};
+
+    fn operator_config() -> String {
+        let mut magic = LicenseMagic::default();
+        magic.randomize_magic(3, 3);
+        let operator = LicenseOperator::builder()
+            .with_properties(LicenseProperties {
+                key_size: 24,
+                magic_size: 3,
+                magic_count: 3,
+            })
+            .with_magic(magic)
+            .with_serializer(Box::new(DefaultLicenseKeySerializer {}))
+            .with_checksum(LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]))
+            .with_features(LicenseFeatures::new(Vec::from(["export_pdf".to_string()])).unwrap())
+            .with_expiration(Box::new(SystemLicenseClock {}))
+            .build()
+            .unwrap();
+
+        LicenseOperatorConfig::from_operator(&operator)
+            .unwrap()
+            .to_toml()
+            .unwrap()
+    }
+
+    #[test]
+    fn validate_python_module() {
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "offline_license").unwrap();
+            offline_license(&module).unwrap();
+
+            let locals = PyDict::new(py);
+            locals.set_item("offline_license", module).unwrap();
+            locals.set_item("config", operator_config()).unwrap();
+            let blacklist_path = std::env::temp_dir()
+                .join(format!("offline_license_python_{}.bin", std::process::id()));
+            let _ = std::fs::remove_file(&blacklist_path);
+            locals.set_item("blacklist_path", &blacklist_path).unwrap();
+
+            py.run(
+                c_str!(
+                    r#"
+LicenseKeyStatus = offline_license.LicenseKeyStatus
+operator = offline_license.LicenseOperator.from_toml(config)
+
+key = operator.generate_license_key(
+    b"sample.name@sample.domain.com",
+    features=["export_pdf"],
+    expires="2149-01-01",
+    issued="2020-01-01",
+)
+assert key.features == ["export_pdf"] and key.has_feature("export_pdf")
+assert (key.issued, key.expires) == ("2020-01-01", "2149-01-01")
+assert str(key) == key.key and operator.parse_key(key.key).seed == key.seed
+assert operator.validate_license_key(key.key) == LicenseKeyStatus.Valid
+assert operator.validate_license_key("not a license key") == LicenseKeyStatus.Invalid
+
+# Blacklist management in blacklist file
+operator = offline_license.LicenseOperator.from_toml(config, blacklist=blacklist_path)
+seed = operator.revoke_key(key.key, reason="refund")
+assert seed == key.seed and operator.blacklist == [seed]
+assert operator.blacklist_entries[0][:2] == (seed, "refund")
+assert operator.validate_license_key(key.key) == LicenseKeyStatus.Blacklisted
+assert not operator.add_seed_to_blacklist(bytes(seed))
+
+# Configuration is not modified, revoked seed is stored in blacklist file
+assert offline_license.LicenseOperator.from_toml(operator.to_toml()).blacklist == []
+restored = offline_license.LicenseOperator.from_toml(config, blacklist=blacklist_path)
+assert restored.validate_license_key(key.key) == LicenseKeyStatus.Blacklisted
+assert restored.remove_seed_from_blacklist(seed) and restored.blacklist == []
+assert not restored.remove_seed_from_blacklist(seed)
+assert restored.validate_license_key(key.key) == LicenseKeyStatus.Valid
+
+# Seeds revoked without blacklist file are kept in memory only
+operator = offline_license.LicenseOperator.from_toml(config)
+assert operator.add_seed_to_blacklist(seed, "chargeback")
+assert operator.validate_license_key(key.key) == LicenseKeyStatus.Blacklisted
+try:
+    operator.remove_seed_from_blacklist(seed)
+    assert False
+except offline_license.LicenseError:
+    pass
+
+# Errors of license operator
+for expires in ["2149-13-01", "2019-01-01", "01.01.2030", None]:
+    try:
+        operator.generate_license_key(b"seed", expires=expires, issued="2020-01-01")
+        assert False
+    except offline_license.LicenseError:
+        pass
+"#
+                ),
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+
+            let _ = std::fs::remove_file(&blacklist_path);
+        });
+    }
+}
@@ -0,0 +1,157 @@
+//! # Offline license WASM
+//!
+//! WebAssembly bindings pre-validating license keys in browser with verifier built from
+//! configuration exported by [LicenseOperatorConfig::from_verifier]. Verifier is built with
+//! default [LicenseRegistry], so configuration has to reference built-in serializer and checksum.
+//!
+//! System time is unavailable in browser, expiration is validated against date of JavaScript
+//! `Date` instead.
+
+use wasm_bindgen::prelude::*;
+
+use offline_license_rs::license_config::{LicenseOperatorConfig, LicenseRegistry};
+use offline_license_rs::license_expiration::{LicenseClock, LicenseDate};
+use offline_license_rs::{license_key, license_verifier};
+
+const MILLISECONDS_PER_DAY: f64 = 86_400_000.0;
+
+/// Status of validated license key, see [LicenseKeyStatus](license_key::LicenseKeyStatus).
+#[wasm_bindgen]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum LicenseKeyStatus {
+    Valid,
+    Invalid,
+    Blacklisted,
+    InvalidSignature,
+    Expired,
+}
+
+impl From<license_key::LicenseKeyStatus> for LicenseKeyStatus {
+    fn from(status: license_key::LicenseKeyStatus) -> Self {
+        match status {
+            license_key::LicenseKeyStatus::Valid => LicenseKeyStatus::Valid,
+            license_key::LicenseKeyStatus::Invalid => LicenseKeyStatus::Invalid,
+            license_key::LicenseKeyStatus::Blacklisted => LicenseKeyStatus::Blacklisted,
+            license_key::LicenseKeyStatus::InvalidSignature => LicenseKeyStatus::InvalidSignature,
+            license_key::LicenseKeyStatus::Expired => LicenseKeyStatus::Expired,
+        }
+    }
+}
+
+/// Clock reading current date from JavaScript `Date`.
+struct JsLicenseClock {}
+
+impl LicenseClock for JsLicenseClock {
+    fn today(&self) -> LicenseDate {
+        let days = (js_sys::Date::now() / MILLISECONDS_PER_DAY).floor();
+        LicenseDate::from_days(days.clamp(0.0, u16::MAX as f64) as u16)
+    }
+}
+
+/// License key parsed by [LicenseVerifier::parse], it is not validated yet.
+#[wasm_bindgen]
+pub struct LicenseKey {
+    key: offline_license_rs::LicenseKey,
+}
+
+#[wasm_bindgen]
+impl LicenseKey {
+    // ==================================================
+    //                Getters & Setters
+    // ==================================================
+
+    /// Seed hash of license key encoded as hexadecimal string.
+    #[wasm_bindgen(getter)]
+    pub fn seed(&self) -> String {
+        hex::encode(&self.key.seed)
+    }
+
+    /// Generation of operator which issued license key, undefined without generation.
+    #[wasm_bindgen(getter)]
+    pub fn generation(&self) -> Option<u8> {
+        self.key.get_generation()
+    }
+
+    /// Names of features enabled by license key.
+    #[wasm_bindgen(getter)]
+    pub fn features(&self) -> Vec<String> {
+        self.key.enabled_features.clone()
+    }
+
+    /// Issue date formatted as `YYYY-MM-DD`, undefined when license key does not expire.
+    #[wasm_bindgen(getter)]
+    pub fn issued(&self) -> Option<String> {
+        self.key
+            .get_expiration()
+            .map(|expiration| expiration.issued.to_string())
+    }
+
+    /// Expiry date formatted as `YYYY-MM-DD`, undefined when license key does not expire.
+    #[wasm_bindgen(getter)]
+    pub fn expires(&self) -> Option<String> {
+        self.key
+            .get_expiration()
+            .map(|expiration| expiration.expires.to_string())
+    }
+
+    #[wasm_bindgen(js_name = hasFeature)]
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.key.has_feature(name)
+    }
+}
+
+/// Verifier of license keys, built from exported configuration of
+/// [LicenseVerifier](license_verifier::LicenseVerifier).
+#[wasm_bindgen]
+pub struct LicenseVerifier {
+    verifier: license_verifier::LicenseVerifier,
+}
+
+#[wasm_bindgen]
+impl LicenseVerifier {
+    // ==================================================
+    //                   Constructor
+    // ==================================================
+
+    #[wasm_bindgen(js_name = fromToml)]
+    pub fn from_toml(config: &str) -> Result<LicenseVerifier, JsError> {
+        Self::build(&LicenseOperatorConfig::from_toml(config)?)
+    }
+
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(config: &str) -> Result<LicenseVerifier, JsError> {
+        Self::build(&LicenseOperatorConfig::from_json(config)?)
+    }
+
+    fn build(config: &LicenseOperatorConfig) -> Result<LicenseVerifier, JsError> {
+        let mut verifier = config.build_verifier(&LicenseRegistry::default())?;
+        if config.expiration {
+            verifier = verifier.with_expiration(Box::new(JsLicenseClock {}));
+        }
+
+        Ok(LicenseVerifier { verifier })
+    }
+
+    // ==================================================
+    //                    Operators
+    // ==================================================
+
+    pub fn parse(&self, key: &str) -> Result<LicenseKey, JsError> {
+        Ok(LicenseKey {
+            key: self.verifier.parse_key(key)?,
+        })
+    }
+
+    /// Validate serialized license key, license key which cannot be parsed is invalid.
+    pub fn validate(&self, key: &str) -> LicenseKeyStatus {
+        match self.verifier.parse_key(key) {
+            Ok(license_key) => self.verifier.validate_license_key(&license_key).into(),
+            Err(_) => LicenseKeyStatus::Invalid,
+        }
+    }
+
+    /// Serialize parsed license key back to license key typed by user.
+    pub fn serialize(&self, key: &LicenseKey) -> String {
+        self.verifier.get_serialized_key(&key.key)
+    }
+}
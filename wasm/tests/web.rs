@@ -0,0 +1,136 @@
+//! Tests of WebAssembly bindings run in headless Node.js runtime by
+//! `cargo test -p offline_license_rs_wasm --target wasm32-unknown-unknown` with
+//! `wasm-bindgen-cli` installed.
+
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use offline_license_rs::license_byte_check::LicenseByteCheck;
+use offline_license_rs::license_checksum::LicenseChecksum;
+use offline_license_rs::license_config::LicenseOperatorConfig;
+use offline_license_rs::license_expiration::{FixedLicenseClock, LicenseDate, LicenseExpiration};
+use offline_license_rs::license_features::LicenseFeatures;
+use offline_license_rs::license_key::LicenseKeyOptions;
+use offline_license_rs::license_magic::LicenseMagic;
+use offline_license_rs::license_operator::LicenseOperator;
+use offline_license_rs::license_properties::LicenseProperties;
+use offline_license_rs::license_serializer::DefaultLicenseKeySerializer;
+use offline_license_rs_wasm::{LicenseKeyStatus, LicenseVerifier};
+
+const USER_EMAIL: &str = "sample.name@sample.domain.com";
+
+fn verifier_config(issuer_op: &LicenseOperator) -> LicenseOperatorConfig {
+    let byte_check = LicenseByteCheck::new(Vec::from([0, 2]), issuer_op.get_magic()).unwrap();
+    LicenseOperatorConfig::from_verifier(&issuer_op.derive_verifier(byte_check)).unwrap()
+}
+
+fn generate_key(issuer_op: &LicenseOperator, seed: &str, options: LicenseKeyOptions) -> String {
+    let license_key = issuer_op
+        .generate_license_key_with_options(seed.as_bytes(), &options)
+        .unwrap();
+    issuer_op.get_serialized_key(&license_key)
+}
+
+fn date(year: i64) -> LicenseDate {
+    LicenseDate::from_ymd(year, 1, 1).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn validate_wasm_verifier_status() {
+    let issuer_op = LicenseOperator::default(3, 3, [1, 2, 3, 4, 5, 6, 7, 8]);
+    let key = generate_key(&issuer_op, USER_EMAIL, LicenseKeyOptions::default());
+
+    let mut config = verifier_config(&issuer_op);
+    let verifier = LicenseVerifier::from_toml(&config.to_toml().unwrap()).unwrap();
+    assert_eq!(verifier.validate(&key), LicenseKeyStatus::Valid);
+
+    // Malformed and tampered license keys are invalid
+    assert_eq!(
+        verifier.validate("not a license key"),
+        LicenseKeyStatus::Invalid
+    );
+    let mut tampered_key = key.clone().into_bytes();
+    tampered_key[0] = match tampered_key[0] {
+        b'0' => b'1',
+        _ => b'0',
+    };
+    assert_eq!(
+        verifier.validate(&String::from_utf8(tampered_key).unwrap()),
+        LicenseKeyStatus::Invalid
+    );
+
+    // Blacklisted seed exported in configuration
+    config.blacklist.push(verifier.parse(&key).unwrap().seed());
+    let verifier = LicenseVerifier::from_json(&config.to_json().unwrap()).unwrap();
+    assert_eq!(verifier.validate(&key), LicenseKeyStatus::Blacklisted);
+
+    assert!(LicenseVerifier::from_toml("magic = 1").is_err());
+}
+
+#[wasm_bindgen_test]
+fn validate_wasm_verifier_parse_and_serialize() {
+    let issuer_op = LicenseOperator::default(3, 3, [1, 2, 3, 4, 5, 6, 7, 8])
+        .with_features(LicenseFeatures::new(Vec::from(["export_pdf".to_string()])).unwrap());
+    let options = LicenseKeyOptions {
+        features: Vec::from(["export_pdf".to_string()]),
+        ..Default::default()
+    };
+    let key = generate_key(&issuer_op, USER_EMAIL, options);
+
+    let verifier =
+        LicenseVerifier::from_toml(&verifier_config(&issuer_op).to_toml().unwrap()).unwrap();
+    let license_key = verifier.parse(&key).unwrap();
+
+    assert_eq!(verifier.serialize(&license_key), key);
+    assert_eq!(license_key.generation(), None);
+    assert_eq!(
+        license_key.features(),
+        Vec::from(["export_pdf".to_string()])
+    );
+    assert!(license_key.has_feature("export_pdf"));
+    assert_eq!(license_key.expires(), None);
+
+    assert!(verifier.parse("not a license key").is_err());
+}
+
+#[wasm_bindgen_test]
+fn validate_wasm_verifier_expiration() {
+    let mut magic = LicenseMagic::default();
+    magic.randomize_magic(3, 3);
+    let issuer_op = LicenseOperator::builder()
+        .with_properties(LicenseProperties {
+            key_size: 24,
+            magic_size: 3,
+            magic_count: 3,
+        })
+        .with_magic(magic)
+        .with_serializer(Box::new(DefaultLicenseKeySerializer {}))
+        .with_checksum(LicenseChecksum::default([1, 2, 3, 4, 5, 6, 7, 8]))
+        .with_expiration(Box::new(FixedLicenseClock { today: date(2020) }))
+        .build()
+        .unwrap();
+    let key_expiring = |expires: i64| {
+        let options = LicenseKeyOptions {
+            expiration: Some(LicenseExpiration::new(date(2020), date(expires)).unwrap()),
+            ..Default::default()
+        };
+        generate_key(&issuer_op, USER_EMAIL, options)
+    };
+
+    // Expiration is validated against JavaScript date
+    let verifier =
+        LicenseVerifier::from_toml(&verifier_config(&issuer_op).to_toml().unwrap()).unwrap();
+    assert_eq!(
+        verifier.validate(&key_expiring(2021)),
+        LicenseKeyStatus::Expired
+    );
+    assert_eq!(
+        verifier.validate(&key_expiring(2149)),
+        LicenseKeyStatus::Valid
+    );
+
+    let license_key = verifier.parse(&key_expiring(2021)).unwrap();
+    assert_eq!(license_key.issued(), Some("2020-01-01".to_string()));
+    assert_eq!(license_key.expires(), Some("2021-01-01".to_string()));
+}